@@ -6,16 +6,47 @@ use std::{
 
 /// A generic sized representation of a function that can be
 /// attached to a Node. The callback will essentially be owned by the element
-pub struct Callback<EVENT, MSG>(Rc<dyn Fn(EVENT) -> MSG>);
+///
+/// A callback can produce no msg or multiple msgs when it is emitted,
+/// ie: an event listener which only cares about the Enter key.
+pub struct Callback<EVENT, MSG> {
+    func: Rc<dyn Fn(EVENT) -> Vec<MSG>>,
+    rate: Option<Rate>,
+}
+
+/// Limits how often the events of an event listener are delivered,
+/// the timers are held by the DOM layer for each element.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rate {
+    /// deliver only the last event once no other event occured
+    /// within this number of milliseconds
+    Debounce(u32),
+    /// deliver only the first event within this number of milliseconds
+    Throttle(u32),
+}
 
 impl<EVENT, F, MSG> From<F> for Callback<EVENT, MSG>
 where
     F: Fn(EVENT) -> MSG + 'static,
 {
     fn from(func: F) -> Self {
-        Callback(Rc::new(func))
+        Callback::with_rate(move |event| vec![func(event)], None)
+    }
+}
+
+impl<EVENT, MSG> Callback<EVENT, MSG> {
+    /// create the callback with the rate at which its events are delivered
+    fn with_rate<F>(func: F, rate: Option<Rate>) -> Self
+    where
+        F: Fn(EVENT) -> Vec<MSG> + 'static,
+    {
+        Callback {
+            func: Rc::new(func),
+            rate,
+        }
     }
 }
+
 impl<EVENT, MSG> fmt::Debug for Callback<EVENT, MSG> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "||{{..}}")
@@ -27,9 +58,43 @@ where
     EVENT: 'static,
     MSG: 'static,
 {
-    /// This method calls the actual callback.
-    pub fn emit<T: Into<EVENT>>(&self, value: T) -> MSG {
-        (self.0)(value.into())
+    /// Create a callback which produces a msg only when the function returns `Some`
+    pub fn from_optional<F>(func: F) -> Self
+    where
+        F: Fn(EVENT) -> Option<MSG> + 'static,
+    {
+        Callback::from_multiple(move |event| func(event).into_iter().collect())
+    }
+
+    /// Create a callback which produces all the msgs returned by the function
+    pub fn from_multiple<F>(func: F) -> Self
+    where
+        F: Fn(EVENT) -> Vec<MSG> + 'static,
+    {
+        Callback::with_rate(func, None)
+    }
+
+    /// This method calls the actual callback and returns the msgs it produced.
+    pub fn emit<T: Into<EVENT>>(&self, value: T) -> Vec<MSG> {
+        (self.func)(value.into())
+    }
+
+    /// Deliver only the last event once no other event occured
+    /// within `millis` milliseconds
+    pub fn debounce(mut self, millis: u32) -> Self {
+        self.rate = Some(Rate::Debounce(millis));
+        self
+    }
+
+    /// Deliver only the first event within `millis` milliseconds
+    pub fn throttle(mut self, millis: u32) -> Self {
+        self.rate = Some(Rate::Throttle(millis));
+        self
+    }
+
+    /// The rate at which the events of this callback are delivered
+    pub fn rate(&self) -> Option<Rate> {
+        self.rate
     }
 
     /// Changes input type of the callback to another.
@@ -38,11 +103,12 @@ where
     where
         F: Fn(EVENT2) -> EVENT + 'static,
     {
+        let rate = self.rate;
         let func_wrap = move |input| {
             let output = func(input);
             self.emit(output)
         };
-        Callback::from(func_wrap)
+        Callback::with_rate(func_wrap, rate)
     }
 
     /// Map the output of this callback to return a different type
@@ -50,11 +116,10 @@ where
     where
         F: Fn(MSG) -> MSG2 + 'static,
     {
-        let func_wrap = move |input| {
-            let out = self.emit(input);
-            func(out)
-        };
-        Callback::from(func_wrap)
+        let rate = self.rate;
+        let func_wrap =
+            move |input| self.emit(input).into_iter().map(&func).collect();
+        Callback::with_rate(func_wrap, rate)
     }
 
     pub fn map_callback<MSG2>(
@@ -64,17 +129,55 @@ where
     where
         MSG2: 'static,
     {
+        let rate = self.rate;
         let func_wrap = move |input| {
-            let out = self.emit(input);
-            cb.emit(out)
+            self.emit(input)
+                .into_iter()
+                .flat_map(|out| cb.emit(out))
+                .collect()
         };
-        Callback::from(func_wrap)
+        Callback::with_rate(func_wrap, rate)
+    }
+}
+
+impl<EVENT, MSG> Callback<EVENT, Option<MSG>>
+where
+    EVENT: 'static,
+    MSG: 'static,
+{
+    /// Convert this callback which returns an optional msg into a callback
+    /// which produces no msg for `None`
+    pub fn optional(self) -> Callback<EVENT, MSG> {
+        let rate = self.rate;
+        Callback::with_rate(
+            move |input| self.emit(input).into_iter().flatten().collect(),
+            rate,
+        )
+    }
+}
+
+impl<EVENT, MSG> Callback<EVENT, Vec<MSG>>
+where
+    EVENT: 'static,
+    MSG: 'static,
+{
+    /// Convert this callback which returns a list of msgs into a callback
+    /// which produces each of the msgs
+    pub fn multiple(self) -> Callback<EVENT, MSG> {
+        let rate = self.rate;
+        Callback::with_rate(
+            move |input| self.emit(input).into_iter().flatten().collect(),
+            rate,
+        )
     }
 }
 
 impl<EVENT, MSG> Clone for Callback<EVENT, MSG> {
     fn clone(&self) -> Self {
-        Callback(Rc::clone(&self.0))
+        Callback {
+            func: Rc::clone(&self.func),
+            rate: self.rate,
+        }
     }
 }
 
@@ -86,6 +189,6 @@ impl<EVENT, MSG> PartialEq for Callback<EVENT, MSG> {
         // diffing algorthmn since all callbacks are effectively called with the closure.into()
         // which are essentially not the same Callback even when they are derived from the same
         // function.
-        Rc::ptr_eq(&self.0, &rhs.0)
+        Rc::ptr_eq(&self.func, &rhs.func) && self.rate == rhs.rate
     }
 }
@@ -33,6 +33,21 @@ where
     EVENT: 'static,
     T: PartialEq,
 {
+    // Lazy nodes with the same props reuse the old rendered node,
+    // there is nothing to be patched in the whole subtree
+    if let (Node::Lazy(old_lazy), Node::Lazy(new_lazy)) = (old, new) {
+        if new_lazy.reuse(old_lazy) {
+            if let Node::Element(old_element) = old.resolve() {
+                for child in old_element.children.iter() {
+                    increment_node_idx_for_children(child, cur_node_idx);
+                }
+            }
+            return vec![];
+        }
+    }
+    let old = old.resolve();
+    let new = new.resolve();
+
     let mut patches = vec![];
     // Different enum variants, replace!
     let mut replace = mem::discriminant(old) != mem::discriminant(new);
@@ -133,6 +148,9 @@ where
         | (Node::Element(_), Node::Text(_)) => {
             unreachable!("Unequal variant discriminants should already have been handled");
         }
+        (Node::Lazy(_), _) | (_, Node::Lazy(_)) => {
+            unreachable!("Lazy nodes should already have been resolved");
+        }
     };
 
     patches
@@ -235,7 +253,7 @@ fn increment_node_idx_for_children<T, EVENT, MSG>(
     cur_node_idx: &mut usize,
 ) {
     *cur_node_idx += 1;
-    if let Node::Element(element_node) = old {
+    if let Node::Element(element_node) = old.resolve() {
         for child in element_node.children.iter() {
             increment_node_idx_for_children(&child, cur_node_idx);
         }
@@ -7,4 +7,11 @@ use std::rc::Rc;
 /// DomUpdater, this will simplify the amount of generics being defined.
 pub trait Dispatch<MSG> {
     fn dispatch(self: &Rc<Self>, msg: MSG);
+
+    /// dispatch all of these msgs, nothing happens when there are no msgs
+    fn dispatch_multiple(self: &Rc<Self>, msgs: Vec<MSG>) {
+        for msg in msgs {
+            self.dispatch(msg);
+        }
+    }
 }
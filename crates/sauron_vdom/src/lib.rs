@@ -9,7 +9,10 @@ mod patch;
 pub(in crate) mod util;
 mod vnode;
 
-pub use callback::Callback;
+pub use callback::{
+    Callback,
+    Rate,
+};
 pub use cmd::Cmd;
 pub use diff::diff;
 pub use dispatch::Dispatch;
@@ -18,6 +21,7 @@ pub use vnode::{
     builder,
     Element,
     Event,
+    Lazy,
     Node,
     Text,
     Value,
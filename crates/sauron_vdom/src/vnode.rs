@@ -12,9 +12,11 @@ pub use attribute::{
     Attribute,
 };
 pub use element::Element;
+pub use lazy::Lazy;
 
 mod attribute;
 mod element;
+mod lazy;
 
 /// This is the core data structure of the library.
 /// Any tree can be represented by `Node`.
@@ -43,6 +45,9 @@ where
 {
     Element(Element<T, EVENT, MSG>),
     Text(Text),
+    /// a node which is rendered from props only when needed,
+    /// and is skipped in the diffing when the props did not change
+    Lazy(Lazy<T, EVENT, MSG>),
 }
 
 impl<T, EVENT, MSG> Node<T, EVENT, MSG>
@@ -54,6 +59,7 @@ where
     where
         F: Fn(MSG) -> MSG2 + 'static,
         MSG2: 'static,
+        T: 'static,
     {
         let cb = Callback::from(func);
         self.map_callback(cb)
@@ -63,10 +69,12 @@ where
     fn map_callback<MSG2>(self, cb: Callback<MSG, MSG2>) -> Node<T, EVENT, MSG2>
     where
         MSG2: 'static,
+        T: 'static,
     {
         match self {
             Node::Element(element) => Node::Element(element.map_callback(cb)),
             Node::Text(text) => Node::Text(Text::new(text.text)),
+            Node::Lazy(lazy) => Node::Lazy(lazy.map_callback(cb)),
         }
     }
 
@@ -77,6 +85,7 @@ where
         match self {
             Node::Element(element) => element.to_pretty_string(indent),
             Node::Text(text) => format!("{}", text),
+            Node::Lazy(lazy) => lazy.node().to_pretty_string(indent),
         }
     }
 
@@ -84,13 +93,23 @@ where
         match self {
             Node::Element(_) => false,
             Node::Text(_) => true,
+            Node::Lazy(lazy) => lazy.node().is_text_node(),
+        }
+    }
+
+    /// the node that is actually rendered,
+    /// lazy nodes are resolved into the node rendered from their props
+    pub fn resolve(&self) -> &Self {
+        match self {
+            Node::Lazy(lazy) => lazy.node().resolve(),
+            _ => self,
         }
     }
 
     pub fn as_element(&mut self) -> Option<&mut Element<T, EVENT, MSG>> {
         match *self {
             Node::Element(ref mut element) => Some(element),
-            Node::Text(_) => None,
+            Node::Text(_) | Node::Lazy(_) => None,
         }
     }
 
@@ -98,6 +117,7 @@ where
         match *self {
             Node::Element(ref element) => Some(element),
             Node::Text(_) => None,
+            Node::Lazy(ref lazy) => lazy.node().as_element_ref(),
         }
     }
 
@@ -129,6 +149,7 @@ where
         match *self {
             Node::Element(ref element) => element.attributes(),
             Node::Text(_) => vec![],
+            Node::Lazy(ref lazy) => lazy.node().get_attributes(),
         }
     }
 }
@@ -61,6 +61,70 @@ where
     pub fn get_callback(&self) -> Option<&Callback<EVENT, MSG>> {
         self.value.get_callback()
     }
+
+    /// Deliver only the last event of this event listener once no other event
+    /// occured within `millis` milliseconds, ie: search as you type.
+    /// ```ignore
+    ///     oninput(|input: InputEvent| Msg::Search(input.value)).debounce(300)
+    /// ```
+    pub fn debounce(self, millis: u32) -> Self {
+        self.map_listener(|cb| cb.debounce(millis))
+    }
+
+    /// Deliver only the first event of this event listener
+    /// within `millis` milliseconds
+    pub fn throttle(self, millis: u32) -> Self {
+        self.map_listener(|cb| cb.throttle(millis))
+    }
+
+    fn map_listener<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Callback<EVENT, MSG>) -> Callback<EVENT, MSG>,
+    {
+        match self.value {
+            AttribValue::Value(_) => self,
+            AttribValue::Callback(cb) => {
+                Attribute::new(self.name, AttribValue::Callback(func(cb)))
+            }
+        }
+    }
+}
+
+impl<EVENT, MSG> Attribute<EVENT, Option<MSG>>
+where
+    MSG: 'static,
+    EVENT: 'static,
+{
+    /// Convert the event listener which returns an optional msg into an event
+    /// listener which dispatches no msg for `None`.
+    /// ```ignore
+    ///     onkeypress(|event: KeyEvent| {
+    ///         if event.key == "Enter" { Some(Msg::Add) } else { None }
+    ///     }).optional()
+    /// ```
+    pub fn optional(self) -> Attribute<EVENT, MSG> {
+        let value = match self.value {
+            AttribValue::Value(value) => AttribValue::Value(value),
+            AttribValue::Callback(cb) => AttribValue::Callback(cb.optional()),
+        };
+        Attribute::new(self.name, value)
+    }
+}
+
+impl<EVENT, MSG> Attribute<EVENT, Vec<MSG>>
+where
+    MSG: 'static,
+    EVENT: 'static,
+{
+    /// Convert the event listener which returns a list of msgs into an event
+    /// listener which dispatches each of the msgs, the view is rendered once for all of them.
+    pub fn multiple(self) -> Attribute<EVENT, MSG> {
+        let value = match self.value {
+            AttribValue::Value(value) => AttribValue::Value(value),
+            AttribValue::Callback(cb) => AttribValue::Callback(cb.multiple()),
+        };
+        Attribute::new(self.name, value)
+    }
 }
 
 impl<EVENT, MSG> AttribValue<EVENT, MSG>
@@ -5,6 +5,7 @@ use crate::{
     },
     Callback,
     Element,
+    Lazy,
     Node,
     Text,
     Value,
@@ -72,6 +73,32 @@ where
     })
 }
 
+/// Create a node which is rendered from the props with the view function.
+/// The view is only called again when the props changed,
+/// otherwise the previously rendered node is reused and skipped in the diffing.
+/// The view can not capture anything other than the props, ie: a function or a closure
+/// without captures.
+///
+///```
+/// use sauron_vdom::{
+///     builder::*,
+///     Node,
+/// };
+/// fn main() {
+///     let row: Node<&'static str, (), ()> = lazy(42, |count: &u32| {
+///         element("li", vec![], vec![text(count)])
+///     });
+/// }
+/// ```
+#[inline]
+pub fn lazy<P, F, T, EVENT, MSG>(props: P, view: F) -> Node<T, EVENT, MSG>
+where
+    P: PartialEq + 'static,
+    F: Fn(&P) -> Node<T, EVENT, MSG> + 'static,
+{
+    Node::Lazy(Lazy::new(props, view))
+}
+
 /// Create an attribute
 #[inline]
 pub fn attr<V, EVENT, MSG>(name: &'static str, v: V) -> Attribute<EVENT, MSG>
@@ -137,6 +137,7 @@ where
     ) -> Element<T, EVENT, MSG2>
     where
         MSG2: 'static,
+        T: 'static,
     {
         Element {
             tag: self.tag,
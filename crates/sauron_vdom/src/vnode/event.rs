@@ -1,11 +1,24 @@
+use std::collections::BTreeMap;
+
 /// A container for generic event and the common values
 /// needed for the user.
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Event {
     MouseEvent(MouseEvent),
     KeyEvent(KeyEvent),
     InputEvent(InputEvent),
+    TouchEvent(TouchEvent),
+    PointerEvent(PointerEvent),
+    DragEvent(DragEvent),
+    WheelEvent(WheelEvent),
+    ScrollEvent(ScrollEvent),
+    ClipboardEvent(ClipboardEvent),
+    FocusEvent(FocusEvent),
+    CompositionEvent(CompositionEvent),
+    MediaEvent(MediaEvent),
+    AnimationEvent(AnimationEvent),
+    TransitionEvent(TransitionEvent),
 }
 
 impl From<MouseEvent> for Event {
@@ -26,22 +39,100 @@ impl From<KeyEvent> for Event {
     }
 }
 
-/// A mouse event contains the (x,y) coordinates, buttons and modifier keys
+impl From<TouchEvent> for Event {
+    fn from(te: TouchEvent) -> Self {
+        Event::TouchEvent(te)
+    }
+}
+
+impl From<PointerEvent> for Event {
+    fn from(pe: PointerEvent) -> Self {
+        Event::PointerEvent(pe)
+    }
+}
+
+impl From<DragEvent> for Event {
+    fn from(de: DragEvent) -> Self {
+        Event::DragEvent(de)
+    }
+}
+
+impl From<WheelEvent> for Event {
+    fn from(we: WheelEvent) -> Self {
+        Event::WheelEvent(we)
+    }
+}
+
+impl From<ScrollEvent> for Event {
+    fn from(se: ScrollEvent) -> Self {
+        Event::ScrollEvent(se)
+    }
+}
+
+impl From<ClipboardEvent> for Event {
+    fn from(ce: ClipboardEvent) -> Self {
+        Event::ClipboardEvent(ce)
+    }
+}
+
+impl From<FocusEvent> for Event {
+    fn from(fe: FocusEvent) -> Self {
+        Event::FocusEvent(fe)
+    }
+}
+
+impl From<CompositionEvent> for Event {
+    fn from(ce: CompositionEvent) -> Self {
+        Event::CompositionEvent(ce)
+    }
+}
+
+impl From<MediaEvent> for Event {
+    fn from(me: MediaEvent) -> Self {
+        Event::MediaEvent(me)
+    }
+}
+
+impl From<AnimationEvent> for Event {
+    fn from(ae: AnimationEvent) -> Self {
+        Event::AnimationEvent(ae)
+    }
+}
+
+impl From<TransitionEvent> for Event {
+    fn from(te: TransitionEvent) -> Self {
+        Event::TransitionEvent(te)
+    }
+}
+
+/// A mouse event contains the type, (x,y) coordinates, buttons and modifier keys.
+/// The related target is the DOM node the pointer entered or exited, its type is supplied
+/// by the user of this crate.
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
-pub struct MouseEvent {
-    pub r#type: &'static str,
+pub struct MouseEvent<TARGET = ()> {
+    /// the name of the event, ie: `click`, `mousemove`, `contextmenu`
+    pub r#type: String,
     pub coordinate: Coordinate,
     pub modifier: Modifier,
-    pub buttons: MouseButton,
+    /// the button which changed state in a mousedown, mouseup or click
+    pub button: MouseButton,
+    /// the bitmask of the buttons which are held down when the event is fired,
+    /// 1: left, 2: right, 4: middle, 8: back, 16: forward
+    pub buttons: u16,
+    /// the target the pointer came from or went to, for mouseover, mouseout, mouseenter and mouseleave
+    pub related_target: Option<TARGET>,
 }
-impl MouseEvent {
+
+impl<TARGET> MouseEvent<TARGET> {
 
     pub fn click(x:i32, y:i32) -> Self {
         MouseEvent{
-            r#type: "click",
+            r#type: "click".to_string(),
             coordinate: Coordinate::new(x,y),
-            //TODO: specify the buttons
-            ..Default::default()
+            modifier: Modifier::default(),
+            button: MouseButton::Left,
+            buttons: 0,
+            related_target: None,
         }
     }
 
@@ -52,14 +143,292 @@ impl MouseEvent {
     pub fn y(&self) -> i32 {
         self.coordinate.y()
     }
+
+    /// check whether this button is held down when the event is fired
+    pub fn is_pressed(&self, button: &MouseButton) -> bool {
+        self.buttons & button.mask() != 0
+    }
+}
+
+/// A touch event contains the touch points which are on the surface,
+/// on the target element and which changed in this event
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TouchEvent {
+    /// the name of the event, ie: `touchstart`, `touchmove`
+    pub r#type: String,
+    /// all the touch points which are currently on the surface
+    pub touches: Vec<Touch>,
+    /// the touch points which started on the target element
+    pub target_touches: Vec<Touch>,
+    /// the touch points which changed in this event,
+    /// ie: the touch points which are lifted in a `touchend`
+    pub changed_touches: Vec<Touch>,
+    pub modifier: Modifier,
+}
+
+/// A single touch point
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct Touch {
+    /// identifies the touch point across the events for as long as it is on the surface
+    pub identifier: i32,
+    pub client_x: i32,
+    pub client_y: i32,
+    pub page_x: i32,
+    pub page_y: i32,
+    pub screen_x: i32,
+    pub screen_y: i32,
+    pub radius_x: i32,
+    pub radius_y: i32,
+    pub rotation_angle: f32,
+    /// the pressure of the touch from 0.0 to 1.0
+    pub force: f32,
+}
+
+/// A pointer event is a mouse event of a mouse, pen or touch pointer
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct PointerEvent<TARGET = ()> {
+    /// the type, coordinates, buttons and modifiers of the event
+    pub mouse: MouseEvent<TARGET>,
+    /// identifies the pointer, used for capturing the pointer
+    pub pointer_id: i32,
+    /// `mouse`, `pen` or `touch`
+    pub pointer_type: String,
+    /// whether this is the primary pointer of its type, ie: the first finger of a multi-touch
+    pub is_primary: bool,
+    /// the pressure of the pointer from 0.0 to 1.0
+    pub pressure: f32,
+    pub tangential_pressure: f32,
+    /// the tilt of the pen in degrees from -90 to 90
+    pub tilt_x: i32,
+    pub tilt_y: i32,
+    /// the rotation of the pen in degrees from 0 to 359
+    pub twist: i32,
+    /// the width of the contact geometry of the pointer
+    pub width: i32,
+    /// the height of the contact geometry of the pointer
+    pub height: i32,
+}
+
+impl<TARGET> PointerEvent<TARGET> {
+    pub fn x(&self) -> i32 {
+        self.mouse.x()
+    }
+
+    pub fn y(&self) -> i32 {
+        self.mouse.y()
+    }
+}
+
+/// A drag event is a mouse event which carries the data being dragged
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DragEvent<TARGET = (), FILE = ()> {
+    /// the type, coordinates, buttons and modifiers of the event
+    pub mouse: MouseEvent<TARGET>,
+    pub data_transfer: DataTransfer<FILE>,
+}
+
+impl<TARGET, FILE> DragEvent<TARGET, FILE> {
+    pub fn x(&self) -> i32 {
+        self.mouse.x()
+    }
+
+    pub fn y(&self) -> i32 {
+        self.mouse.y()
+    }
+}
+
+/// The data being dragged, the files are supplied by the user of this crate
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataTransfer<FILE = ()> {
+    /// the effect of the drop: `none`, `copy`, `link` or `move`
+    pub drop_effect: String,
+    /// the effects which are allowed by the drag source, ie: `copyMove`, `all`
+    pub effect_allowed: String,
+    /// the formats of the data, `Files` is included when files are dragged
+    pub types: Vec<String>,
+    pub items: Vec<DataTransferItem>,
+    /// the string payloads and their format. The browser only exposes the payloads
+    /// in `dragstart` and `drop`, for the other drag events this is empty.
+    pub data: Vec<(String, String)>,
+    /// the files which are dragged from outside of the page
+    pub files: Vec<FILE>,
+}
+
+/// implemented manually, since the files don't need to have a default
+impl<FILE> Default for DataTransfer<FILE> {
+    fn default() -> Self {
+        DataTransfer {
+            drop_effect: String::new(),
+            effect_allowed: String::new(),
+            types: vec![],
+            items: vec![],
+            data: vec![],
+            files: vec![],
+        }
+    }
+}
+
+impl<FILE> DataTransfer<FILE> {
+    /// get the string payload of this format
+    pub fn get_data(&self, format: &str) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|(data_format, _)| data_format == format)
+            .map(|(_, data)| data.as_str())
+    }
+}
+
+/// An item of the data being dragged
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct DataTransferItem {
+    /// `string` or `file`
+    pub kind: String,
+    /// the format of the item, ie: `text/plain`, `image/png`
+    pub r#type: String,
+}
+
+/// A wheel event contains the amount scrolled, in the unit of the delta mode
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct WheelEvent<TARGET = ()> {
+    /// the type, coordinates, buttons and modifiers of the event
+    pub mouse: MouseEvent<TARGET>,
+    pub delta_x: f64,
+    pub delta_y: f64,
+    pub delta_z: f64,
+    pub delta_mode: DeltaMode,
+}
+
+/// The unit of the deltas in a wheel event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeltaMode {
+    Pixel,
+    Line,
+    Page,
+}
+
+impl Default for DeltaMode {
+    fn default() -> Self {
+        DeltaMode::Pixel
+    }
+}
+
+impl From<u32> for DeltaMode {
+    fn from(delta_mode: u32) -> Self {
+        match delta_mode {
+            1 => DeltaMode::Line,
+            2 => DeltaMode::Page,
+            _ => DeltaMode::Pixel,
+        }
+    }
+}
+
+/// A scroll event contains the scroll position together with the size of the content
+/// and the visible size of the scrolled element
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ScrollEvent {
+    pub scroll_top: i32,
+    pub scroll_left: i32,
+    /// the width of the whole content
+    pub scroll_width: i32,
+    /// the height of the whole content
+    pub scroll_height: i32,
+    /// the visible width of the element
+    pub client_width: i32,
+    /// the visible height of the element
+    pub client_height: i32,
+}
+
+impl ScrollEvent {
+    /// the distance in pixels from the bottom of the visible part
+    /// to the bottom of the content, ie: for loading more items in an infinite scroll
+    pub fn scroll_bottom(&self) -> i32 {
+        self.scroll_height - self.scroll_top - self.client_height
+    }
+}
+
+/// A clipboard event contains the type and the text data of the clipboard
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardEvent {
+    /// the name of the event, ie: `copy`, `cut`, `paste`
+    pub r#type: String,
+    /// the plain text in the clipboard, ie: the pasted text
+    pub data: Option<String>,
+}
+
+/// A focus event contains the type and the id of the element
+/// which lost or received the focus in exchange
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FocusEvent {
+    /// the name of the event, ie: `focus`, `blur`, `focusin`, `focusout`
+    pub r#type: String,
+    /// the id of the element the focus came from or went to,
+    /// `None` when the element has no id or the focus left the document
+    pub related_target_id: Option<String>,
+}
+
+/// A composition event of an input method editor, ie: when entering CJK text
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct CompositionEvent {
+    /// the name of the event, ie: `compositionstart`, `compositionupdate`, `compositionend`
+    pub r#type: String,
+    /// the text being composed, or the committed text at `compositionend`
+    pub data: String,
+}
+
+/// A media event contains the playback state of the audio or video element
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct MediaEvent {
+    /// the name of the event, ie: `play`, `pause`, `timeupdate`, `ended`
+    pub r#type: String,
+    /// the playback position in seconds
+    pub current_time: f64,
+    /// the length of the media in seconds, `NaN` while the metadata is not yet loaded
+    pub duration: f64,
+    pub paused: bool,
+    pub ended: bool,
+    /// the volume from 0.0 (silent) to 1.0 (loudest)
+    pub volume: f64,
+    pub muted: bool,
+}
+
+/// A CSS animation event
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct AnimationEvent {
+    /// the name of the event, ie: `animationstart`, `animationiteration`, `animationend`
+    pub r#type: String,
+    /// the name of the `@keyframes` of the animation
+    pub animation_name: String,
+    /// the number of seconds the animation has been running
+    pub elapsed_time: f32,
+    /// the pseudo element the animation runs on, ie: `::before`, empty for the element itself
+    pub pseudo_element: String,
+}
+
+/// A CSS transition event
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TransitionEvent {
+    /// the name of the event, ie: `transitionstart`, `transitionend`
+    pub r#type: String,
+    /// the name of the transitioned CSS property, ie: `opacity`
+    pub property_name: String,
+    /// the number of seconds the transition has been running
+    pub elapsed_time: f32,
+    /// the pseudo element the transition runs on, ie: `::before`, empty for the element itself
+    pub pseudo_element: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct KeyEvent {
+    /// the value of the key, ie: `a`, `A`, `Enter`
     pub key: String,
+    /// the physical key on the keyboard regardless of the layout, ie: `KeyA`, `Enter`
+    pub code: String,
     pub modifier: Modifier,
     pub repeat: bool,
     pub location: u32,
+    /// the key is pressed while composing text with an input method,
+    /// ie: entering chinese characters
+    pub is_composing: bool,
 }
 
 impl KeyEvent {
@@ -69,6 +438,82 @@ impl KeyEvent {
             ..Default::default()
         }
     }
+
+    /// the typed value of the key
+    pub fn named_key(&self) -> Key {
+        Key::from(self.key.as_str())
+    }
+}
+
+/// The value of a key, with the common named keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Key {
+    Enter,
+    Escape,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    Space,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Shift,
+    Control,
+    Alt,
+    Meta,
+    CapsLock,
+    /// the function keys F1 to F12
+    F(u8),
+    /// a key which produces a single character, ie: `a`, `A`, `1`, `+`
+    Character(char),
+    /// the other named keys
+    Other(String),
+}
+
+impl From<&str> for Key {
+    fn from(key: &str) -> Self {
+        match key {
+            "Enter" => Key::Enter,
+            "Escape" | "Esc" => Key::Escape,
+            "Tab" => Key::Tab,
+            "Backspace" => Key::Backspace,
+            "Delete" | "Del" => Key::Delete,
+            "Insert" => Key::Insert,
+            " " | "Spacebar" => Key::Space,
+            "ArrowUp" | "Up" => Key::ArrowUp,
+            "ArrowDown" | "Down" => Key::ArrowDown,
+            "ArrowLeft" | "Left" => Key::ArrowLeft,
+            "ArrowRight" | "Right" => Key::ArrowRight,
+            "Home" => Key::Home,
+            "End" => Key::End,
+            "PageUp" => Key::PageUp,
+            "PageDown" => Key::PageDown,
+            "Shift" => Key::Shift,
+            "Control" => Key::Control,
+            "Alt" => Key::Alt,
+            "Meta" => Key::Meta,
+            "CapsLock" => Key::CapsLock,
+            _ => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Key::Character(c),
+                    (Some('F'), Some(_)) => {
+                        match key[1..].parse::<u8>() {
+                            Ok(n) if (1..=12).contains(&n) => Key::F(n),
+                            _ => Key::Other(key.to_string()),
+                        }
+                    }
+                    _ => Key::Other(key.to_string()),
+                }
+            }
+        }
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -76,6 +521,36 @@ pub struct InputEvent {
     pub value: String,
 }
 
+/// The fields of a submitted form, each field name can have multiple values,
+/// ie: a `<select multiple>` or checkboxes with the same name
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FormValues {
+    pub values: BTreeMap<String, Vec<String>>,
+}
+
+impl FormValues {
+    /// add a value to this field
+    pub fn insert(&mut self, name: String, value: String) {
+        self.values.entry(name).or_default().push(value);
+    }
+
+    /// the first value of this field
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .get(name)
+            .and_then(|values| values.first())
+            .map(|value| value.as_str())
+    }
+
+    /// all the values of this field
+    pub fn get_all(&self, name: &str) -> &[String] {
+        self.values
+            .get(name)
+            .map(|values| values.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
 impl InputEvent{
 
     pub fn new(value: String) -> Self {
@@ -90,10 +565,25 @@ pub enum MouseButton {
     Left,
     Right,
     Middle,
+    /// the fourth button, which is usually the browser back button
     WheelUp,
+    /// the fifth button, which is usually the browser forward button
     WheelDown,
 }
 
+impl MouseButton {
+    /// the bit of this button in the `buttons` bitmask of the mouse event
+    pub fn mask(&self) -> u16 {
+        match self {
+            MouseButton::Left => 1,
+            MouseButton::Right => 2,
+            MouseButton::Middle => 4,
+            MouseButton::WheelUp => 8,
+            MouseButton::WheelDown => 16,
+        }
+    }
+}
+
 impl Default for MouseButton {
 
     fn default() -> Self {
@@ -109,6 +599,8 @@ pub struct Coordinate {
     pub movement_y: i32,
     pub offset_x: i32,
     pub offset_y: i32,
+    pub page_x: i32,
+    pub page_y: i32,
     pub screen_x: i32,
     pub screen_y: i32,
     pub x: i32,
@@ -0,0 +1,179 @@
+use crate::{
+    Callback,
+    Node,
+};
+use std::{
+    any::{
+        Any,
+        TypeId,
+    },
+    cell::OnceCell,
+    fmt,
+    mem,
+    rc::Rc,
+};
+
+type PropsEq = fn(&dyn Any, &dyn Any) -> bool;
+type View<T, EVENT, MSG> = Rc<dyn Fn(&dyn Any) -> Node<T, EVENT, MSG>>;
+
+/// A node which is derived from the props only.
+/// The view is called only when the node is needed, and the diffing will
+/// reuse the previously rendered node and skip the whole subtree
+/// if the props of the old and the new lazy node are equal.
+pub struct Lazy<T, EVENT, MSG>
+where
+    MSG: 'static,
+    EVENT: 'static,
+{
+    /// the type of the original view function,
+    /// props are only comparable when they are rendered with the same view
+    view_type: TypeId,
+    props: Rc<dyn Any>,
+    props_eq: PropsEq,
+    /// the callbacks this lazy node is mapped with, compared the same way as the props
+    callbacks: Vec<(Rc<dyn Any>, PropsEq)>,
+    view: View<T, EVENT, MSG>,
+    rendered: OnceCell<Rc<Node<T, EVENT, MSG>>>,
+}
+
+impl<T, EVENT, MSG> Lazy<T, EVENT, MSG>
+where
+    EVENT: 'static,
+    MSG: 'static,
+{
+    /// The view must not capture anything, ie: a function or a closure without captures,
+    /// so the rendered node can only depend on the props.
+    pub fn new<P, F>(props: P, view: F) -> Self
+    where
+        P: PartialEq + 'static,
+        F: Fn(&P) -> Node<T, EVENT, MSG> + 'static,
+    {
+        assert_eq!(
+            mem::size_of::<F>(),
+            0,
+            "the view of a lazy node can not capture anything other than the props"
+        );
+        Lazy {
+            view_type: TypeId::of::<F>(),
+            props: Rc::new(props),
+            props_eq: props_eq::<P>,
+            callbacks: vec![],
+            view: Rc::new(move |props: &dyn Any| {
+                view(
+                    props.downcast_ref::<P>().expect("props of the wrong type"),
+                )
+            }),
+            rendered: OnceCell::new(),
+        }
+    }
+
+    /// the node rendered from the props,
+    /// the view is called the first time this is accessed
+    pub fn node(&self) -> &Node<T, EVENT, MSG> {
+        self.rendered()
+    }
+
+    fn rendered(&self) -> &Rc<Node<T, EVENT, MSG>> {
+        self.rendered
+            .get_or_init(|| Rc::new((self.view)(&*self.props)))
+    }
+
+    /// checks whether the old lazy node is rendered from equal props
+    /// with the same view and mapped with the same callbacks as this lazy node
+    pub fn is_same(&self, old: &Self) -> bool {
+        self.view_type == old.view_type
+            && (self.props_eq)(&*self.props, &*old.props)
+            && self.callbacks.len() == old.callbacks.len()
+            && self
+                .callbacks
+                .iter()
+                .zip(old.callbacks.iter())
+                .all(|((cb, cb_eq), (old_cb, _))| cb_eq(&**cb, &**old_cb))
+    }
+
+    /// Reuse the rendered node of the old lazy node if they have the same props.
+    /// Returns true if the old rendered node is reused.
+    pub(crate) fn reuse(&self, old: &Self) -> bool {
+        if self.is_same(old) {
+            // this will fail if this lazy node is already rendered
+            self.rendered.set(Rc::clone(old.rendered())).is_ok()
+        } else {
+            false
+        }
+    }
+
+    /// map_callback the return of the callback from MSG to MSG2,
+    /// the node will be rendered again from the props when needed.
+    /// Since the callbacks are only equal to their clones, the mapped lazy node
+    /// is reused only when it is mapped with a clone of the same callback.
+    pub(super) fn map_callback<MSG2>(
+        self,
+        cb: Callback<MSG, MSG2>,
+    ) -> Lazy<T, EVENT, MSG2>
+    where
+        MSG2: 'static,
+        T: 'static,
+    {
+        let view = self.view;
+        let mut callbacks = self.callbacks;
+        callbacks.push((
+            Rc::new(cb.clone()),
+            props_eq::<Callback<MSG, MSG2>> as PropsEq,
+        ));
+        Lazy {
+            view_type: self.view_type,
+            props: self.props,
+            props_eq: self.props_eq,
+            callbacks,
+            view: Rc::new(move |props: &dyn Any| {
+                view(props).map_callback(cb.clone())
+            }),
+            rendered: OnceCell::new(),
+        }
+    }
+}
+
+fn props_eq<P>(props: &dyn Any, other: &dyn Any) -> bool
+where
+    P: PartialEq + 'static,
+{
+    match (props.downcast_ref::<P>(), other.downcast_ref::<P>()) {
+        (Some(props), Some(other)) => props == other,
+        _ => false,
+    }
+}
+
+impl<T, EVENT, MSG> Clone for Lazy<T, EVENT, MSG> {
+    fn clone(&self) -> Self {
+        Lazy {
+            view_type: self.view_type,
+            props: Rc::clone(&self.props),
+            props_eq: self.props_eq,
+            callbacks: self.callbacks.clone(),
+            view: Rc::clone(&self.view),
+            rendered: self.rendered.clone(),
+        }
+    }
+}
+
+impl<T, EVENT, MSG> PartialEq for Lazy<T, EVENT, MSG>
+where
+    T: PartialEq,
+    EVENT: PartialEq,
+    MSG: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.is_same(other) || self.node() == other.node()
+    }
+}
+
+impl<T, EVENT, MSG> fmt::Debug for Lazy<T, EVENT, MSG>
+where
+    T: fmt::Debug,
+    EVENT: fmt::Debug,
+    MSG: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Lazy({:?})", self.node())
+    }
+}
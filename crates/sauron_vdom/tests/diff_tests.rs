@@ -130,3 +130,60 @@ fn add_event() {
         vec![Patch::AddEventListener(0, vec![&on("click", cb)])]
     )
 }
+
+#[test]
+fn lazy_node_with_same_props_is_skipped() {
+    use std::cell::Cell;
+    thread_local! {
+        static VIEW_COUNT: Cell<u32> = Cell::new(0);
+    }
+    let view_count = || VIEW_COUNT.with(|count| count.get());
+    let row = |count: u32| {
+        builder::lazy(count, |count: &u32| {
+            VIEW_COUNT.with(|view_count| view_count.set(view_count.get() + 1));
+            builder::element("li", vec![], vec![builder::text(count)])
+        })
+    };
+    let old: Node<&'static str, (), ()> =
+        builder::element("ul", vec![], vec![row(1), row(2)]);
+    let new: Node<&'static str, (), ()> =
+        builder::element("ul", vec![], vec![row(1), row(3)]);
+    // rendering the old lazy nodes
+    assert!(!old.to_string().is_empty());
+    assert_eq!(view_count(), 2);
+
+    let diff = diff(&old, &new);
+    // only the changed row is rendered again
+    assert_eq!(view_count(), 3);
+    assert_eq!(
+        diff,
+        vec![Patch::ChangeText(4, &Text::new("3"))],
+        "Should only patch the changed lazy node"
+    );
+}
+
+#[test]
+fn mapped_lazy_node_is_rendered_again() {
+    use std::cell::Cell;
+    thread_local! {
+        static VIEW_COUNT: Cell<u32> = Cell::new(0);
+    }
+    let view_count = || VIEW_COUNT.with(|count| count.get());
+    // the row index captured in map_msg may be different for the same props
+    let row = |index: u32| -> Node<&'static str, (), u32> {
+        builder::lazy(1, |count: &u32| {
+            VIEW_COUNT.with(|view_count| view_count.set(view_count.get() + 1));
+            builder::element("li", vec![], vec![builder::text(count)])
+        })
+        .map_msg(move |msg: u32| msg + index)
+    };
+    let old = builder::element("ul", vec![], vec![row(1)]);
+    let new = builder::element("ul", vec![], vec![row(2)]);
+    assert!(!old.to_string().is_empty());
+    assert_eq!(view_count(), 1);
+
+    let diff = diff(&old, &new);
+    // the callback of map_msg is not the same, so the view is called again
+    assert_eq!(view_count(), 2);
+    assert_eq!(diff, vec![]);
+}
@@ -33,7 +33,6 @@ pub enum Msg {
     ToggleEdit(usize),
     Toggle(usize),
     ClearCompleted,
-    Nope,
 }
 
 impl Model {
@@ -92,7 +91,6 @@ impl Component<Msg> for Model {
             Msg::ClearCompleted => {
                 self.clear_completed();
             }
-            Msg::Nope => {}
         }
         Cmd::none()
     }
@@ -237,11 +235,12 @@ impl Model {
                 oninput(|v: InputEvent| Msg::Update(v.value)),
                 onkeypress(|event: KeyEvent| {
                     if event.key == "Enter" {
-                        Msg::Add
+                        Some(Msg::Add)
                     } else {
-                        Msg::Nope
+                        None
                     }
-                }),
+                })
+                .optional(),
             ],
             vec![],
         )
@@ -300,11 +299,12 @@ fn view_entry_edit_input((idx, entry): (usize, &Entry)) -> Node<Msg> {
                 onblur(move |_| Msg::Edit(idx)),
                 onkeypress(move |event: KeyEvent| {
                     if event.key == "Enter" {
-                        Msg::Edit(idx)
+                        Some(Msg::Edit(idx))
                     } else {
-                        Msg::Nope
+                        None
                     }
-                }),
+                })
+                .optional(),
             ],
             vec![],
         )
@@ -34,7 +34,6 @@ pub enum Msg {
     ToggleEdit(usize),
     Toggle(usize),
     ClearCompleted,
-    Nope,
 }
 
 impl Model {
@@ -93,7 +92,6 @@ impl Component<Msg> for Model {
             Msg::ClearCompleted => {
                 self.clear_completed();
             }
-            Msg::Nope => {}
         }
         Cmd::none()
     }
@@ -232,11 +230,12 @@ impl Model {
                 oninput(|v: InputEvent| Msg::Update(v.value)),
                 onkeypress(|event: KeyEvent| {
                     if event.key == "Enter" {
-                        Msg::Add
+                        Some(Msg::Add)
                     } else {
-                        Msg::Nope
+                        None
                     }
-                }),
+                })
+                .optional(),
             ],
             [],
         )
@@ -292,11 +291,12 @@ fn view_entry_edit_input((idx, entry): (usize, &Entry)) -> Node<Msg> {
                 onblur(move |_| Msg::Edit(idx)),
                 onkeypress(move |event: KeyEvent| {
                     if event.key == "Enter" {
-                        Msg::Edit(idx)
+                        Some(Msg::Edit(idx))
                     } else {
-                        Msg::Nope
+                        None
                     }
-                }),
+                })
+                .optional(),
             ],
             [],
         )
@@ -1,6 +1,9 @@
 #![deny(warnings)]
 use app::Model;
-use sauron::Program;
+use sauron::{
+    Program,
+    Scheduling,
+};
 use wasm_bindgen::prelude::*;
 
 mod app;
@@ -16,5 +19,5 @@ pub fn main() {
         console_error_panic_hook::set_once();
     }
     sauron::log!("in main!");
-    Program::mount_to_body(Model::new());
+    Program::mount_to_body_with_scheduling(Model::new(), Scheduling::Sync);
 }
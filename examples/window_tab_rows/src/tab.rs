@@ -1,7 +1,4 @@
-use crate::row::{
-    self,
-    Row,
-};
+use crate::row::Row;
 use sauron::{
     html::{
         attributes::*,
@@ -17,12 +14,10 @@ use sauron::Cmd;
 #[derive(Debug, PartialEq, Clone)]
 pub enum Msg {
     TabClick,
-    RowMsg(usize, row::Msg),
 }
 
 pub struct Tab {
     tab_clicks: u32,
-    rows: Vec<Row>,
     is_active: bool,
     pub name: String,
     pub color: String,
@@ -32,10 +27,6 @@ impl Tab {
     pub fn new(name: &str, color: &str) -> Self {
         Tab {
             tab_clicks: 0,
-            rows: (0..10)
-                .into_iter()
-                .map(|index| Row::new(format!("Row {}", index)))
-                .collect(),
             is_active: false,
             name: name.to_string(),
             color: color.to_string(),
@@ -58,9 +49,6 @@ impl Component<Msg> for Tab {
             Msg::TabClick => {
                 self.tab_clicks += 1;
             }
-            Msg::RowMsg(index, row_msg) => {
-                self.rows[index].update(row_msg);
-            }
         }
         Cmd::none()
     }
@@ -83,15 +71,16 @@ impl Component<Msg> for Tab {
                         self.name, self.tab_clicks
                     ))],
                 ),
+                // the rows are stateful child components which
+                // handles their own messages
                 div(
                     vec![class("rows")],
-                    self.rows
-                        .iter()
-                        .enumerate()
-                        .map(|(index, row)| {
-                            row.view().map_msg(move |row_msg| {
-                                Msg::RowMsg(index, row_msg)
-                            })
+                    (0..10)
+                        .map(|index| {
+                            component(
+                                format!("{}-row-{}", self.name, index),
+                                move || Row::new(format!("Row {}", index)),
+                            )
                         })
                         .collect::<Vec<Node<Msg>>>(),
                 ),
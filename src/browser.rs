@@ -2,11 +2,22 @@ use crate::{
     Cmd,
     Component,
     Dispatch,
+    NodeRef,
+};
+use std::{
+    fmt::Debug,
+    rc::Rc,
 };
-use std::fmt::Debug;
 use wasm_bindgen::{
     closure::Closure,
     JsCast,
+    JsValue,
+};
+use web_sys::{
+    CustomEvent,
+    CustomEventInit,
+    EventTarget,
+    HtmlMediaElement,
 };
 
 /// provides an interface for doing url request, such as fetch
@@ -22,19 +33,208 @@ impl Browser {
     {
         let cmd: Cmd<APP, MSG> = Cmd::new(move |program| {
             let cb_clone = cb.clone();
+            let program_clone = Rc::clone(&program);
             let resize_callback: Closure<dyn Fn(web_sys::Event)> =
                 Closure::wrap(Box::new(move |_| {
                     let (window_width, window_height) = Self::get_size();
                     let msg = cb_clone(window_width, window_height);
-                    program.dispatch(msg);
+                    program_clone.dispatch(msg);
                 }));
             crate::window()
-                .set_onresize(Some(resize_callback.as_ref().unchecked_ref()));
-            resize_callback.forget();
+                .add_event_listener_with_callback(
+                    "resize",
+                    resize_callback.as_ref().unchecked_ref(),
+                )
+                .expect("Unable to add resize event listener");
+            // the listener is removed and the closure dropped
+            // when the program is unmounted
+            program.add_teardown(move || {
+                crate::window()
+                    .remove_event_listener_with_callback(
+                        "resize",
+                        resize_callback.as_ref().unchecked_ref(),
+                    )
+                    .expect("Unable to remove resize event listener");
+            });
         });
         cmd
     }
 
+    /// Capture the pointer to the element of the node ref, so the following pointer events
+    /// of this pointer are targeted at the element even when the pointer leaves it,
+    /// ie: for dragging a slider or drawing on a canvas.
+    /// ```ignore
+    ///     Msg::StartDrawing(pointer_id) => {
+    ///         Browser::set_pointer_capture(&self.canvas, pointer_id)
+    ///     }
+    /// ```
+    pub fn set_pointer_capture<APP, MSG>(
+        node_ref: &NodeRef,
+        pointer_id: i32,
+    ) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let node_ref = node_ref.clone();
+        Cmd::new(move |_program| {
+            if let Some(element) = node_ref.get() {
+                if let Err(error) = element.set_pointer_capture(pointer_id) {
+                    crate::log!("Unable to capture the pointer: {:?}", error);
+                }
+            }
+        })
+    }
+
+    /// Release the pointer which is captured by the element of the node ref
+    pub fn release_pointer_capture<APP, MSG>(
+        node_ref: &NodeRef,
+        pointer_id: i32,
+    ) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let node_ref = node_ref.clone();
+        Cmd::new(move |_program| {
+            if let Some(element) = node_ref.get() {
+                if let Err(error) = element.release_pointer_capture(pointer_id)
+                {
+                    crate::log!("Unable to release the pointer: {:?}", error);
+                }
+            }
+        })
+    }
+
+    /// Start playing the audio or video element of the node ref
+    /// ```ignore
+    ///     Msg::Play => Browser::play_media(&self.video),
+    /// ```
+    pub fn play_media<APP, MSG>(node_ref: &NodeRef) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        Self::media_cmd(node_ref, |media| {
+            match media.play() {
+                Ok(promise) => {
+                    // the promise is rejected when the browser blocks the autoplay
+                    let log_rejection =
+                        Closure::once(|error: wasm_bindgen::JsValue| {
+                            crate::log!(
+                                "Unable to play the media: {:?}",
+                                error
+                            );
+                        });
+                    let _ = promise.catch(&log_rejection);
+                    log_rejection.forget();
+                }
+                Err(error) => {
+                    crate::log!("Unable to play the media: {:?}", error);
+                }
+            }
+        })
+    }
+
+    /// Pause the audio or video element of the node ref
+    pub fn pause_media<APP, MSG>(node_ref: &NodeRef) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        Self::media_cmd(node_ref, |media| {
+            if let Err(error) = media.pause() {
+                crate::log!("Unable to pause the media: {:?}", error);
+            }
+        })
+    }
+
+    /// Move the playback position of the audio or video element to `time` in seconds
+    pub fn seek_media<APP, MSG>(node_ref: &NodeRef, time: f64) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        Self::media_cmd(node_ref, move |media| media.set_current_time(time))
+    }
+
+    /// Set the volume of the audio or video element,
+    /// from 0.0 (silent) to 1.0 (loudest)
+    pub fn set_media_volume<APP, MSG>(
+        node_ref: &NodeRef,
+        volume: f64,
+    ) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        // `max` and `min` also turn NaN into 0.0, setting NaN as the volume throws
+        let volume = volume.max(0.0).min(1.0);
+        Self::media_cmd(node_ref, move |media| media.set_volume(volume))
+    }
+
+    /// Execute the action on the media element of the node ref,
+    /// nothing happens when the node ref is not attached to an audio or video element
+    fn media_cmd<APP, MSG, F>(node_ref: &NodeRef, action: F) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+        F: Fn(&HtmlMediaElement) + 'static,
+    {
+        let node_ref = node_ref.clone();
+        Cmd::new(move |_program| {
+            let media = node_ref.get().and_then(|element| {
+                element.dyn_into::<HtmlMediaElement>().ok()
+            });
+            if let Some(media) = media {
+                action(&media);
+            }
+        })
+    }
+
+    /// Dispatch a `CustomEvent` with this name and detail from the element of the node ref,
+    /// the event bubbles so JS code outside of the program can listen to it.
+    /// ```ignore
+    ///     Msg::Save => Browser::dispatch_custom_event(
+    ///         &self.editor,
+    ///         "editor-save",
+    ///         JsValue::from_str(&self.content),
+    ///     ),
+    /// ```
+    pub fn dispatch_custom_event<APP, MSG>(
+        node_ref: &NodeRef,
+        event_name: &str,
+        detail: JsValue,
+    ) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let node_ref = node_ref.clone();
+        let event_name = event_name.to_string();
+        Cmd::new(move |_program| {
+            if let Some(element) = node_ref.get() {
+                dispatch_custom_event(&element, &event_name, &detail);
+            }
+        })
+    }
+
+    /// Dispatch a `CustomEvent` with this name and detail from the root node of the program
+    pub fn dispatch_custom_event_from_root<APP, MSG>(
+        event_name: &str,
+        detail: JsValue,
+    ) -> Cmd<APP, MSG>
+    where
+        MSG: 'static,
+        APP: Component<MSG> + 'static,
+    {
+        let event_name = event_name.to_string();
+        Cmd::new(move |program| {
+            let root_node = program.dom_updater.borrow().root_node();
+            dispatch_custom_event(&root_node, &event_name, &detail);
+        })
+    }
+
     fn get_size() -> (i32, i32) {
         let window = crate::window();
         let window_width = window
@@ -50,3 +250,23 @@ impl Browser {
         (window_width as i32, window_height as i32)
     }
 }
+
+/// Dispatch a `CustomEvent` from the target, the event bubbles
+/// and crosses the shadow root boundaries
+pub(crate) fn dispatch_custom_event(
+    target: &EventTarget,
+    event_name: &str,
+    detail: &JsValue,
+) {
+    let event = CustomEvent::new_with_event_init_dict(
+        event_name,
+        CustomEventInit::new()
+            .bubbles(true)
+            .composed(true)
+            .detail(detail),
+    )
+    .expect("Unable to create custom event");
+    target
+        .dispatch_event(&event)
+        .expect("Unable to dispatch custom event");
+}
@@ -2,6 +2,19 @@ use crate::{
     Cmd,
     Node,
 };
+pub use child::component;
+pub(crate) use child::{
+    ChildComponents,
+    MountedChild,
+};
+pub use error_boundary::{
+    catch_view,
+    error_boundary,
+    ViewError,
+};
+
+pub(crate) mod child;
+mod error_boundary;
 
 /// The app should implement this trait for it to be handled by the Program
 pub trait Component<MSG>
@@ -21,3 +34,17 @@ where
     /// Returns a node on how the component is presented.
     fn view(&self) -> Node<MSG>;
 }
+
+/// A lightweight component which is rendered only from its props.
+///
+/// Place it in the view with `html::stateless`, the `view` is called again only when
+/// the props are not equal to the props in the previous view, otherwise the previously
+/// rendered node is reused and the whole subtree is skipped in the diffing.
+/// The messages are handled by the parent component.
+pub trait StatelessComponent<MSG>: PartialEq + 'static
+where
+    MSG: 'static,
+{
+    /// Returns a node on how the props are presented.
+    fn view(&self) -> Node<MSG>;
+}
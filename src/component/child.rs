@@ -0,0 +1,227 @@
+//! Stateful child components which are embedded in the view of a parent component.
+//!
+//! Each child component is a `Program` of its own, mounted into a host element in the
+//! parent's DOM. The child programs are kept in a registry keyed by the key supplied in the
+//! parent view, so the state of the child survives the re-rendering of the parent.
+//! A child component is dropped when its key is no longer present in the parent's view.
+use crate::{
+    html::{
+        attributes::{
+            attr,
+            key as key_attr,
+        },
+        div,
+    },
+    Component,
+    Node,
+    Program,
+};
+use std::{
+    cell::RefCell,
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    rc::Rc,
+};
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+
+pub(crate) const DATA_SAURON_COMPONENT: &str = "data-sauron-component";
+
+/// A child program that is mounted in a host element
+pub(crate) trait MountedChild {
+    /// the root node of the child program
+    fn root_node(&self) -> web_sys::Node;
+    /// tear down the child program
+    fn unmount(&self);
+}
+
+type MountFn = Box<dyn FnOnce(&Element) -> Rc<dyn MountedChild>>;
+
+enum Slot {
+    /// the child is rendered in the view, but is not mounted yet
+    Pending(MountFn),
+    Mounted(Rc<dyn MountedChild>),
+}
+
+thread_local! {
+    static OWNER_UNIQUE_ID: RefCell<u32> = RefCell::new(0);
+    static SLOTS: RefCell<HashMap<String, Slot>> = RefCell::new(HashMap::new());
+    /// the owners which are currently calling their view,
+    /// together with the keys of the child components they rendered
+    static RENDER_STACK: RefCell<Vec<(u32, HashSet<String>)>> = RefCell::new(vec![]);
+}
+
+/// Place a stateful child component in the view.
+///
+/// The component is created with `init` the first time it is rendered, after that the same
+/// instance is reused for as long as the parent view contains a child component with this key.
+/// The child component handles its own messages.
+/// ```ignore
+///     div(vec![], vec![component("counter", || Counter::new(0))])
+/// ```
+pub fn component<K, F, APP, CMSG, MSG>(key: K, init: F) -> Node<MSG>
+where
+    K: ToString,
+    F: FnOnce() -> APP + 'static,
+    APP: Component<CMSG> + 'static,
+    CMSG: 'static,
+{
+    let owner = RENDER_STACK
+        .with(|stack| stack.borrow().last().map(|(owner, _)| *owner))
+        .unwrap_or(0);
+    let full_key = format!("{}:{}", owner, key.to_string());
+
+    RENDER_STACK.with(|stack| {
+        if let Some((_, keys)) = stack.borrow_mut().last_mut() {
+            keys.insert(full_key.clone());
+        }
+    });
+    SLOTS.with(|slots| {
+        slots
+            .borrow_mut()
+            .entry(full_key.clone())
+            .or_insert_with(|| {
+                Slot::Pending(Box::new(move |host: &Element| {
+                    let program: Rc<dyn MountedChild> =
+                        Program::new_append_to_mount(init(), host);
+                    program
+                }))
+            });
+    });
+
+    div(
+        vec![
+            key_attr(full_key.clone()),
+            attr(DATA_SAURON_COMPONENT, full_key),
+        ],
+        vec![],
+    )
+}
+
+/// Mount the child components of the hosts in this newly inserted node.
+/// This is called after the node is inserted in the document, so the on_mount events and
+/// node refs of the child components are dispatched when they are already in the document.
+pub(crate) fn attach_all(node: &web_sys::Node) {
+    if node.node_type() != web_sys::Node::ELEMENT_NODE {
+        return;
+    }
+    let element: &Element = node.unchecked_ref();
+    let mut hosts = vec![];
+    if is_host(node) {
+        hosts.push(element.clone());
+    }
+    if let Ok(descendants) =
+        element.query_selector_all(&format!("[{}]", DATA_SAURON_COMPONENT))
+    {
+        for i in 0..descendants.length() {
+            if let Some(descendant) = descendants.item(i) {
+                hosts.push(descendant.unchecked_into());
+            }
+        }
+    }
+    for host in hosts {
+        attach(&host);
+    }
+}
+
+/// Mount the child component of this host element.
+/// If the child component is already mounted elsewhere, its DOM is moved into this host.
+fn attach(host: &Element) {
+    let key = match host.get_attribute(DATA_SAURON_COMPONENT) {
+        Some(key) => key,
+        None => return,
+    };
+    // the slot is taken out, since mounting the child program
+    // will render the view of the child which may also contain child components
+    let slot = SLOTS.with(|slots| slots.borrow_mut().remove(&key));
+    let child = match slot {
+        Some(Slot::Pending(mount)) => mount(host),
+        Some(Slot::Mounted(child)) => {
+            host.append_child(&child.root_node())
+                .expect("Unable to move child component");
+            child
+        }
+        None => return,
+    };
+    SLOTS.with(|slots| slots.borrow_mut().insert(key, Slot::Mounted(child)));
+}
+
+/// Keeps track of the child components rendered by a program
+pub(crate) struct ChildComponents {
+    owner: u32,
+    rendered: RefCell<HashSet<String>>,
+    unused: RefCell<Vec<String>>,
+}
+
+impl ChildComponents {
+    pub fn new() -> Self {
+        let owner = OWNER_UNIQUE_ID.with(|unique_id| {
+            let mut unique_id = unique_id.borrow_mut();
+            *unique_id += 1;
+            *unique_id
+        });
+        ChildComponents {
+            owner,
+            rendered: RefCell::new(HashSet::new()),
+            unused: RefCell::new(vec![]),
+        }
+    }
+
+    /// call the view while recording the child components that are rendered in it
+    pub fn render<F, N>(&self, view: F) -> N
+    where
+        F: FnOnce() -> N,
+    {
+        RENDER_STACK.with(|stack| {
+            stack.borrow_mut().push((self.owner, HashSet::new()))
+        });
+        let node = view();
+        let (_owner, keys) = RENDER_STACK.with(|stack| {
+            stack.borrow_mut().pop().expect("must have a render frame")
+        });
+        let mut rendered = self.rendered.borrow_mut();
+        self.unused
+            .borrow_mut()
+            .extend(rendered.difference(&keys).cloned());
+        *rendered = keys;
+        node
+    }
+
+    /// drop the child components which are no longer in the view.
+    /// This is called after the dom is updated with the view.
+    pub fn remove_unused(&self) {
+        let unused: Vec<String> = self.unused.borrow_mut().drain(..).collect();
+        for key in unused {
+            remove_slot(&key);
+        }
+    }
+
+    /// drop all the child components of this owner,
+    /// This is called when the owner program is unmounted.
+    pub fn unmount_all(&self) {
+        let rendered: Vec<String> =
+            self.rendered.borrow_mut().drain().collect();
+        let unused: Vec<String> = self.unused.borrow_mut().drain(..).collect();
+        for key in rendered.into_iter().chain(unused) {
+            remove_slot(&key);
+        }
+    }
+}
+
+/// remove the child component of this key and unmount it
+fn remove_slot(key: &str) {
+    let slot = SLOTS.with(|slots| slots.borrow_mut().remove(key));
+    if let Some(Slot::Mounted(child)) = slot {
+        child.unmount();
+    }
+}
+
+/// checks whether this node is a host of a child component,
+/// the children of the host is managed by the child program
+pub(crate) fn is_host(node: &web_sys::Node) -> bool {
+    node.dyn_ref::<Element>()
+        .map(|element| element.has_attribute(DATA_SAURON_COMPONENT))
+        .unwrap_or(false)
+}
@@ -0,0 +1,121 @@
+//! Error boundaries keep a failing part of the view from taking down the whole app.
+//!
+//! The view of the part is evaluated in `error_boundary`, when it returns an error the
+//! fallback is rendered in its place and the error is reported to the owning component as a msg,
+//! which is dispatched once the fallback is mounted.
+//! The view and the fallback are keyed differently, so the fallback is always mounted anew
+//! instead of being patched from the view when they have the same tag.
+//! A view which panics can be turned into a fallible view with `catch_view`.
+use crate::{
+    html::{
+        attributes::key,
+        events::on_mount,
+        span,
+    },
+    Node,
+};
+use std::fmt;
+
+/// The error of a part of the view which failed to render
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewError {
+    message: String,
+}
+
+impl ViewError {
+    /// create a view error with this message
+    pub fn new<S: ToString>(message: S) -> Self {
+        ViewError {
+            message: message.to_string(),
+        }
+    }
+
+    /// the message of the error
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ViewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Render the fallible view, when it fails the fallback is rendered instead and the msg
+/// created by `on_error` is dispatched to the program after the fallback is mounted.
+/// The msg is dispatched again only when the fallback is mounted anew, so re-rendering
+/// the same failing view does not flood the program with msgs.
+/// ```ignore
+///     error_boundary(
+///         || catch_view(|| self.chart.view()),
+///         |error| p(vec![], vec![text(format!("Unable to show the chart: {}", error))]),
+///         Msg::ChartFailed,
+///     )
+/// ```
+pub fn error_boundary<MSG, V, F, E>(
+    view: V,
+    fallback: F,
+    on_error: E,
+) -> Node<MSG>
+where
+    MSG: 'static,
+    V: FnOnce() -> Result<Node<MSG>, ViewError>,
+    F: FnOnce(&ViewError) -> Node<MSG>,
+    E: Fn(ViewError) -> MSG + 'static,
+{
+    match view() {
+        Ok(mut node) => {
+            if let Some(element) = node.as_element() {
+                if element.get_attr_value("key").is_none() {
+                    element.add_attributes(vec![key("error-boundary-view")]);
+                }
+            }
+            node
+        }
+        Err(error) => {
+            let mut fallback_node = fallback(&error);
+            // a different error is reported again, since the fallback is replaced
+            let attributes = vec![
+                key(format!("error-boundary-fallback: {}", error)),
+                on_mount(move |_| on_error(error.clone())),
+            ];
+            match fallback_node.as_element() {
+                Some(element) => {
+                    element.add_attributes(attributes);
+                    fallback_node
+                }
+                // text and lazy nodes can not have event listeners
+                None => span(attributes, vec![fallback_node]),
+            }
+        }
+    }
+}
+
+/// Call the view, catching the panic in it as an error.
+/// Panics can only be caught when the crate is compiled with `panic = "unwind"`,
+/// which is not supported in `wasm32-unknown-unknown`, there the panic still aborts the program.
+/// Prefer returning errors from the view over panicking.
+pub fn catch_view<MSG, V>(view: V) -> Result<Node<MSG>, ViewError>
+where
+    V: FnOnce() -> Node<MSG>,
+{
+    #[cfg(panic = "unwind")]
+    {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(view)).map_err(
+            |payload| {
+                if let Some(message) = payload.downcast_ref::<&str>() {
+                    ViewError::new(message)
+                } else if let Some(message) = payload.downcast_ref::<String>() {
+                    ViewError::new(message)
+                } else {
+                    ViewError::new("the view panicked")
+                }
+            },
+        )
+    }
+    #[cfg(not(panic = "unwind"))]
+    {
+        Ok(view())
+    }
+}
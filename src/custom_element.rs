@@ -0,0 +1,267 @@
+//! Export a Component as a custom element (Web Component), so it can be used
+//! in pages which are built with other frameworks.
+//!
+//! A new instance of the component is created each time the custom element is connected to the
+//! document and its view is mounted in the shadow root of the element.
+//! The instance is torn down when the element is disconnected from the document.
+use crate::{
+    Cmd,
+    Component,
+    Dispatch,
+    Program,
+};
+use js_sys::{
+    Array,
+    Function,
+    Reflect,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
+use wasm_bindgen::{
+    closure::Closure,
+    JsCast,
+    JsValue,
+};
+use web_sys::{
+    HtmlElement,
+    ShadowRoot,
+    ShadowRootInit,
+    ShadowRootMode,
+};
+
+/// the property of the custom element which holds the id of its instance
+const SAURON_INSTANCE_ID: &str = "sauronInstanceId";
+
+/// The class of the custom element, the lifecycle callbacks of the element
+/// are delegated to the closures supplied from rust.
+/// This is evaluated with the `Function` constructor, which requires `'unsafe-eval'`.
+const CUSTOM_ELEMENT_CLASS: &str = r#"
+    return class extends HTMLElement {
+        static get observedAttributes() {
+            return observed;
+        }
+        connectedCallback() {
+            connected(this);
+        }
+        disconnectedCallback() {
+            disconnected(this);
+        }
+        attributeChangedCallback(name, oldValue, newValue) {
+            attributeChanged(this, name, oldValue, newValue);
+        }
+    }
+"#;
+
+/// A component which can be registered as a custom element
+pub trait CustomElement<MSG>: Component<MSG>
+where
+    MSG: 'static,
+{
+    /// the html attributes of the custom element which are observed for changes
+    fn observed_attributes() -> Vec<&'static str>;
+
+    /// Called when an observed attribute is set, changed or removed.
+    /// The returned msg is dispatched to the component.
+    /// This is also called with the values of the attributes that are already set
+    /// when the element is connected.
+    fn attribute_changed(
+        attr_name: &str,
+        old_value: Option<String>,
+        new_value: Option<String>,
+    ) -> Option<MSG>;
+}
+
+type AttributeChanged = Box<dyn Fn(&str, Option<String>, Option<String>)>;
+
+struct Instance {
+    attribute_changed: AttributeChanged,
+    unmount: Box<dyn Fn()>,
+}
+
+thread_local! {
+    static INSTANCE_UNIQUE_ID: RefCell<u32> = RefCell::new(0);
+    static INSTANCES: RefCell<HashMap<u32, Rc<Instance>>> = RefCell::new(HashMap::new());
+}
+
+/// Register the component as a custom element with this tag name.
+/// The tag name must contain a hyphen, ie: `sauron-counter`.
+///
+/// Note: the class of the custom element is created at runtime with the `Function`
+/// constructor, so this fails on pages whose Content Security Policy does not allow
+/// `'unsafe-eval'` in `script-src`. A JS snippet is not used, since snippets are not
+/// supported when building with `wasm-pack --target no-modules`.
+/// ```ignore
+///     custom_element::register("sauron-counter", || Counter::new(0));
+/// ```
+pub fn register<APP, MSG, F>(tag_name: &str, init: F)
+where
+    APP: CustomElement<MSG> + 'static,
+    MSG: 'static,
+    F: Fn() -> APP + 'static,
+{
+    let connected: Closure<dyn Fn(HtmlElement)> =
+        Closure::wrap(Box::new(move |element: HtmlElement| {
+            connect(&element, init());
+        }));
+    let disconnected: Closure<dyn Fn(HtmlElement)> =
+        Closure::wrap(Box::new(move |element: HtmlElement| {
+            disconnect(&element);
+        }));
+    let attribute_changed: Closure<
+        dyn Fn(HtmlElement, String, JsValue, JsValue),
+    > = Closure::wrap(Box::new(
+        move |element: HtmlElement,
+              name: String,
+              old_value: JsValue,
+              new_value: JsValue| {
+            // the instance is cloned out of the registry, since dispatching the msg
+            // may also connect or disconnect other custom elements
+            let instance = instance_id(&element).and_then(|instance_id| {
+                INSTANCES.with(|instances| {
+                    instances.borrow().get(&instance_id).map(Rc::clone)
+                })
+            });
+            if let Some(instance) = instance {
+                (instance.attribute_changed)(
+                    &name,
+                    old_value.as_string(),
+                    new_value.as_string(),
+                );
+            }
+        },
+    ));
+    let observed: Array = APP::observed_attributes()
+        .into_iter()
+        .map(JsValue::from)
+        .collect();
+
+    let class_factory = Function::new_with_args(
+        "connected, disconnected, attributeChanged, observed",
+        CUSTOM_ELEMENT_CLASS,
+    );
+    let args = Array::of4(
+        connected.as_ref(),
+        disconnected.as_ref(),
+        attribute_changed.as_ref(),
+        &observed,
+    );
+    let class: Function = class_factory
+        .apply(&JsValue::NULL, &args)
+        .expect("Unable to create the custom element class")
+        .unchecked_into();
+    crate::window()
+        .custom_elements()
+        .define(tag_name, &class)
+        .expect("Unable to define the custom element");
+    // the custom element class lives as long as the page
+    connected.forget();
+    disconnected.forget();
+    attribute_changed.forget();
+}
+
+/// Mount a new program of the app in the shadow root of the element
+fn connect<APP, MSG>(element: &HtmlElement, app: APP)
+where
+    APP: CustomElement<MSG> + 'static,
+    MSG: 'static,
+{
+    let shadow_root: ShadowRoot = element.shadow_root().unwrap_or_else(|| {
+        element
+            .attach_shadow(&ShadowRootInit::new(ShadowRootMode::Open))
+            .expect("Unable to attach shadow root")
+    });
+    let program = Program::new_append_to_mount(app, &shadow_root);
+    for attr_name in APP::observed_attributes() {
+        if let Some(value) = element.get_attribute(attr_name) {
+            if let Some(msg) =
+                APP::attribute_changed(attr_name, None, Some(value))
+            {
+                program.dispatch(msg);
+            }
+        }
+    }
+
+    let instance_id = INSTANCE_UNIQUE_ID.with(|unique_id| {
+        let mut unique_id = unique_id.borrow_mut();
+        *unique_id += 1;
+        *unique_id
+    });
+    Reflect::set(
+        element,
+        &JsValue::from_str(SAURON_INSTANCE_ID),
+        &JsValue::from(instance_id),
+    )
+    .expect("Unable to set the instance id");
+
+    let program_clone = Rc::clone(&program);
+    let instance = Instance {
+        attribute_changed: Box::new(move |attr_name, old_value, new_value| {
+            if let Some(msg) =
+                APP::attribute_changed(attr_name, old_value, new_value)
+            {
+                program_clone.dispatch(msg);
+            }
+        }),
+        unmount: Box::new(move || program.unmount()),
+    };
+    INSTANCES.with(|instances| {
+        instances
+            .borrow_mut()
+            .insert(instance_id, Rc::new(instance))
+    });
+}
+
+/// Tear down the program of this element
+fn disconnect(element: &HtmlElement) {
+    if let Some(instance_id) = instance_id(element) {
+        let instance = INSTANCES
+            .with(|instances| instances.borrow_mut().remove(&instance_id));
+        if let Some(instance) = instance {
+            (instance.unmount)();
+        }
+        let _ = Reflect::delete_property(
+            element,
+            &JsValue::from_str(SAURON_INSTANCE_ID),
+        );
+    }
+}
+
+fn instance_id(element: &HtmlElement) -> Option<u32> {
+    Reflect::get(element, &JsValue::from_str(SAURON_INSTANCE_ID))
+        .ok()
+        .and_then(|id| id.as_f64())
+        .map(|id| id as u32)
+}
+
+/// Dispatch a CustomEvent with this name and detail from the custom element
+/// which hosts the program. The event bubbles and crosses the shadow root boundary,
+/// so it can be listened to by the page which uses the custom element.
+/// ```ignore
+///     fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+///         match msg {
+///             Msg::Increment => {
+///                 self.count += 1;
+///                 custom_element::emit_event("count-changed", JsValue::from(self.count))
+///             }
+///         }
+///     }
+/// ```
+pub fn emit_event<APP, MSG>(event_name: &str, detail: JsValue) -> Cmd<APP, MSG>
+where
+    APP: Component<MSG> + 'static,
+    MSG: 'static,
+{
+    let event_name = event_name.to_string();
+    Cmd::new(move |program| {
+        let root_node = program.dom_updater.borrow().root_node();
+        // the program is mounted in the shadow root of the custom element
+        let target = match root_node.get_root_node().dyn_into::<ShadowRoot>() {
+            Ok(shadow_root) => shadow_root.host().unchecked_into(),
+            Err(_) => root_node,
+        };
+        crate::browser::dispatch_custom_event(&target, &event_name, &detail);
+    })
+}
@@ -1,9 +1,15 @@
-use crate::Dispatch;
+use crate::{
+    Dispatch,
+    Measurements,
+};
 use apply_patches::patch;
+pub(crate) use apply_patches::DATA_SAURON_UNMANAGED;
 use sauron_vdom::{
     self,
     diff,
     Callback,
+    Patch,
+    Rate,
 };
 use std::{
     collections::HashMap,
@@ -25,6 +31,15 @@ use web_sys::{
 };
 
 mod apply_patches;
+mod error;
+mod node_ref;
+mod portal;
+
+pub use error::PatchError;
+pub(crate) use node_ref::DATA_SAURON_REF;
+pub use node_ref::NodeRef;
+pub(crate) use portal::DATA_SAURON_PORTAL;
+use portal::Portal;
 
 // Used to uniquely identify elements that contain closures so that the DomUpdater can
 // look them up by their unique id.
@@ -37,6 +52,11 @@ lazy_static! {
 
 pub(self) const DATA_SAURON_VDOM_ID: &str = "data-sauron-vdom-id";
 
+/// The event dispatched to an element right after it is inserted into the DOM
+pub(crate) const MOUNT_EVENT: &str = "mount";
+/// The event dispatched to an element right before it is removed from the DOM
+pub(crate) const UNMOUNT_EVENT: &str = "unmount";
+
 /// Closures that we are holding on to to make sure that they don't get invalidated after a
 /// VirtualNode is dropped.
 ///
@@ -75,6 +95,8 @@ where
     /// FIXME: Drop them when the element is no longer in the page. Need to figure out
     /// a good strategy for when to do this.
     pub active_closures: ActiveClosure,
+    /// the portals in the current_vdom, which are mounted outside of the root node
+    portals: Vec<Portal<DSP, MSG>>,
     _phantom_dsp: PhantomData<DSP>,
 }
 
@@ -109,6 +131,9 @@ impl<T> CreatedNode<T> {
                     Self::create_element_node(program, element_node).into();
                 created_element
             }
+            crate::Node::Lazy(lazy) => {
+                Self::create_dom_node(program, lazy.node())
+            }
         }
     }
 
@@ -178,7 +203,7 @@ impl<T> CreatedNode<T> {
 
         let mut previous_node_was_text = false;
         for child in velem.children.iter() {
-            match child {
+            match child.resolve() {
                 crate::Node::Text(text_node) => {
                     let current_node = element.as_ref() as &web_sys::Node;
 
@@ -213,6 +238,9 @@ impl<T> CreatedNode<T> {
                         .append_child(&child_elem)
                         .expect("Unable to append element node");
                 }
+                crate::Node::Lazy(_) => {
+                    unreachable!("lazy node should already be resolved")
+                }
             }
         }
 
@@ -223,6 +251,12 @@ impl<T> CreatedNode<T> {
     }
 }
 
+/// The events which default action is not prevented,
+/// preventing the default of a `dragstart` or `drag` cancels the drag operation
+/// and preventing the default of a clipboard event cancels the copy, cut or paste.
+const KEEP_DEFAULT_EVENTS: [&str; 5] =
+    ["dragstart", "drag", "copy", "cut", "paste"];
+
 /// This wrap into a closure the function that is dispatched when the event is triggered.
 fn create_closure_wrap<DSP, MSG>(
     program: &Rc<DSP>,
@@ -234,19 +268,169 @@ where
 {
     let callback_clone = callback.clone();
     let program_clone = Rc::clone(&program);
+    // the time the last event was delivered, for throttled listeners
+    let mut last_delivered: Option<f64> = None;
+    // the last event which is not yet delivered, for debounced listeners
+    let mut pending = PendingTimeout::default();
 
     Closure::wrap(Box::new(move |event: web_sys::Event| {
         // stop propagation to the containers of this element to have
         // a more fine grain control and expected results
         event.stop_propagation();
-        // prevent the reloading the page in href links
-        event.prevent_default();
-        let cb_event = crate::Event(event);
-        let msg = callback_clone.emit(cb_event);
-        program_clone.dispatch(msg);
+        // prevent the reloading the page in href links,
+        // except for the events which default action is needed
+        if !KEEP_DEFAULT_EVENTS.contains(&event.type_().as_str()) {
+            event.prevent_default();
+        }
+        match callback_clone.rate() {
+            None => {
+                let msgs = callback_clone.emit(crate::Event(event));
+                program_clone.dispatch_multiple(msgs);
+            }
+            Some(Rate::Throttle(millis)) => {
+                let now = crate::now();
+                let within_window = last_delivered
+                    .map(|last| now - last < f64::from(millis))
+                    .unwrap_or(false);
+                if !within_window {
+                    last_delivered = Some(now);
+                    let msgs = callback_clone.emit(crate::Event(event));
+                    program_clone.dispatch_multiple(msgs);
+                }
+            }
+            Some(Rate::Debounce(millis)) => {
+                // the msgs are extracted right away while the event is still current,
+                // they are dispatched only if no other event is received in the meantime
+                let msgs = callback_clone.emit(crate::Event(event));
+                let program = Rc::clone(&program_clone);
+                let deliver = Closure::once(move || {
+                    program.dispatch_multiple(msgs);
+                });
+                pending.replace(millis, deliver);
+            }
+        }
     }))
 }
 
+/// The timeout which delivers the last event of a debounced listener.
+/// The timeout is cleared when it is replaced by a newer event and when the
+/// event listener is removed, since the closure of the listener is then dropped.
+#[derive(Default)]
+struct PendingTimeout {
+    timeout: Option<(i32, Closure<dyn FnMut()>)>,
+}
+
+impl PendingTimeout {
+    fn replace(&mut self, millis: u32, deliver: Closure<dyn FnMut()>) {
+        self.clear();
+        let handle = crate::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                deliver.as_ref().unchecked_ref(),
+                millis as i32,
+            );
+        match handle {
+            Ok(handle) => self.timeout = Some((handle, deliver)),
+            Err(error) => crate::log!("Unable to set the timeout: {:?}", error),
+        }
+    }
+
+    fn clear(&mut self) {
+        if let Some((handle, _deliver)) = self.timeout.take() {
+            crate::window().clear_timeout_with_handle(handle);
+        }
+    }
+}
+
+impl Drop for PendingTimeout {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Dispatch the mount event to the elements of this newly inserted node
+/// which are listening to it, parent elements first.
+/// The child components and the node refs of the elements are attached
+/// before the mount event is dispatched.
+pub(crate) fn dispatch_mount(node: &Node, closures: &ActiveClosure) {
+    crate::component::child::attach_all(node);
+    node_ref::bind_all(node);
+    let mut vdom_ids: Vec<u32> = closures
+        .iter()
+        .filter(|(_vdom_id, events)| {
+            events.iter().any(|(event, _)| *event == MOUNT_EVENT)
+        })
+        .map(|(vdom_id, _)| *vdom_id)
+        .collect();
+    // vdom ids are assigned to the parent before its children
+    vdom_ids.sort();
+    for vdom_id in vdom_ids {
+        if let Some(element) = find_element_with_vdom_id(node, vdom_id) {
+            dispatch_lifecycle_event(&element, MOUNT_EVENT);
+        }
+    }
+}
+
+/// Dispatch the unmount event to this node and all of it's descendants
+/// which are listening to it, child elements first.
+/// This is called right before the node is removed from the DOM.
+pub(crate) fn dispatch_unmount(node: &Node, closures: &ActiveClosure) {
+    if node.node_type() != Node::ELEMENT_NODE {
+        return;
+    }
+    let element: &Element = node.unchecked_ref();
+    let mut elements = vec![element.clone()];
+    if let Ok(descendants) =
+        element.query_selector_all(&format!("[{}]", DATA_SAURON_VDOM_ID))
+    {
+        for i in 0..descendants.length() {
+            if let Some(descendant) = descendants.item(i) {
+                elements.push(descendant.unchecked_into());
+            }
+        }
+    }
+    for element in elements.iter().rev() {
+        let has_unmount_listener = element
+            .get_attribute(DATA_SAURON_VDOM_ID)
+            .and_then(|vdom_id| vdom_id.parse::<u32>().ok())
+            .and_then(|vdom_id| closures.get(&vdom_id))
+            .map(|events| {
+                events.iter().any(|(event, _)| *event == UNMOUNT_EVENT)
+            })
+            .unwrap_or(false);
+        if has_unmount_listener {
+            dispatch_lifecycle_event(element, UNMOUNT_EVENT);
+        }
+    }
+}
+
+fn find_element_with_vdom_id(node: &Node, vdom_id: u32) -> Option<Element> {
+    if node.node_type() != Node::ELEMENT_NODE {
+        return None;
+    }
+    let element: &Element = node.unchecked_ref();
+    let vdom_id_str = vdom_id.to_string();
+    if element.get_attribute(DATA_SAURON_VDOM_ID).as_ref() == Some(&vdom_id_str)
+    {
+        Some(element.clone())
+    } else {
+        element
+            .query_selector(&format!(
+                "[{}=\"{}\"]",
+                DATA_SAURON_VDOM_ID, vdom_id_str
+            ))
+            .ok()
+            .and_then(|found| found)
+    }
+}
+
+fn dispatch_lifecycle_event(element: &Element, event_name: &str) {
+    let event =
+        web_sys::Event::new(event_name).expect("Unable to create event");
+    element
+        .dispatch_event(&event)
+        .expect("Unable to dispatch lifecycle event");
+}
+
 impl<DSP, MSG> DomUpdater<DSP, MSG>
 where
     MSG: 'static,
@@ -254,13 +438,16 @@ where
 {
     /// Creates and instance of this DOM updater, but doesn't mount the current_vdom to the DOM just yet.
     pub fn new(
-        current_vdom: crate::Node<MSG>,
+        mut current_vdom: crate::Node<MSG>,
         root_node: &Node,
     ) -> DomUpdater<DSP, MSG> {
+        let portal_contents = portal::take_contents(&mut current_vdom);
+        let portals = Portal::create_all(portal_contents);
         DomUpdater {
             current_vdom,
             root_node: root_node.clone(),
             active_closures: ActiveClosure::new(),
+            portals,
             _phantom_dsp: PhantomData,
         }
     }
@@ -283,8 +470,10 @@ where
         self.root_node
             .append_child(&created_node.node)
             .expect("Could not append child to mount");
+        dispatch_mount(&created_node.node, &created_node.closures);
         self.root_node = created_node.node;
         self.active_closures = created_node.closures;
+        self.mount_portals(program);
     }
 
     /// Mount the current_vdom replacing the actual browser DOM specified in the root_node
@@ -297,8 +486,16 @@ where
         root_element
             .replace_with_with_node_1(&created_node.node)
             .expect("Could not append child to mount");
+        dispatch_mount(&created_node.node, &created_node.closures);
         self.root_node = created_node.node;
         self.active_closures = created_node.closures;
+        self.mount_portals(program);
+    }
+
+    fn mount_portals(&mut self, program: &Rc<DSP>) {
+        for portal in self.portals.iter_mut() {
+            portal.mount(program);
+        }
     }
 
     /// Create a new `DomUpdater`.
@@ -333,21 +530,150 @@ where
     ///
     /// Then use that diff to patch the real DOM in the user's browser so that they are
     /// seeing the latest state of the application.
+    /// If the patching failed, the error is logged and the DOM is rendered anew.
     pub fn update_dom(
         &mut self,
         program: &Rc<DSP>,
         new_vdom: crate::Node<MSG>,
     ) {
+        if let Err(error) = self.try_update_dom(program, new_vdom) {
+            crate::log!("Error in patching the dom: {}", error);
+        }
+    }
+
+    /// Same as `update_dom`, but the error of the patching is returned.
+    /// The DOM is already rendered anew from the new virtual dom when an error is returned.
+    pub fn try_update_dom(
+        &mut self,
+        program: &Rc<DSP>,
+        new_vdom: crate::Node<MSG>,
+    ) -> Result<(), PatchError> {
+        self.update_dom_measured(program, new_vdom, None)
+    }
+
+    /// Same as `try_update_dom`, this also records the durations of the diffing and patching
+    /// and the patch, node and closure counts in the measurements, if there are any.
+    pub(crate) fn update_dom_measured(
+        &mut self,
+        program: &Rc<DSP>,
+        mut new_vdom: crate::Node<MSG>,
+        measurements: Option<&mut Measurements>,
+    ) -> Result<(), PatchError> {
+        let measuring = measurements.is_some();
+        let t1 = if measuring { crate::now() } else { 0.0 };
+        let portal_contents = portal::take_contents(&mut new_vdom);
         let patches = diff(&self.current_vdom, &new_vdom);
-        let active_closures = patch(
+        let t2 = if measuring { crate::now() } else { 0.0 };
+        // the position of the root node, in case it will be replaced
+        let root_parent = self.root_node.parent_node();
+        let root_previous_sibling = self.root_node.previous_sibling();
+        let closures_before = if measuring {
+            self.active_closure_len()
+        } else {
+            0
+        };
+        let patched = patch(
             program,
             self.root_node.clone(),
+            &self.current_vdom,
             &mut self.active_closures,
             &patches,
-        )
-        .expect("Error in patching the dom");
+        );
+        let (active_closures, reconciled_closures, patch_error) = match patched
+        {
+            Ok((active_closures, reconciled_closures)) => {
+                (active_closures, reconciled_closures, None)
+            }
+            Err(error) => (ActiveClosure::new(), 0, Some(error)),
+        };
+        let closures_created = active_closures
+            .values()
+            .map(|closures| closures.len())
+            .sum::<usize>()
+            + reconciled_closures
+            + patches
+                .iter()
+                .map(|patch| match patch {
+                    Patch::AddEventListener(_, events) => events.len(),
+                    _ => 0,
+                })
+                .sum::<usize>();
         self.active_closures.extend(active_closures);
+        if let Some(measurements) = measurements {
+            measurements.diff_took = t2 - t1;
+            measurements.patch_took = crate::now() - t2;
+            measurements.patch_count = patches.len();
+            measurements.node_count = node_count(&new_vdom);
+            measurements.closures_created = closures_created;
+            // the closures of a rerendered DOM are not counted as created
+            measurements.closures_dropped = (closures_before
+                + closures_created)
+                .saturating_sub(self.active_closure_len());
+        }
         self.current_vdom = new_vdom;
+        if self.root_node.parent_node().is_none() {
+            // the root node has been replaced, the new root node
+            // is at the same position as the old one
+            let new_root_node = match (root_previous_sibling, root_parent) {
+                (Some(previous_sibling), _) => previous_sibling.next_sibling(),
+                (None, Some(parent)) => parent.first_child(),
+                (None, None) => None,
+            };
+            if let Some(new_root_node) = new_root_node {
+                self.root_node = new_root_node;
+            }
+        }
+        if patch_error.is_some() {
+            self.rerender(program);
+        }
+        // the patch errors of the portals are also returned,
+        // after the patch error of the root node
+        let portal_error =
+            Portal::update_all(&mut self.portals, program, portal_contents)
+                .err();
+        match patch_error.or(portal_error) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Recover from a failed patching by replacing the root node with
+    /// a DOM node freshly created from the current vdom.
+    /// The closures of the old DOM are all dropped.
+    fn rerender(&mut self, program: &Rc<DSP>) {
+        let created_node: CreatedNode<Node> =
+            CreatedNode::<Node>::create_dom_node(program, &self.current_vdom);
+        dispatch_unmount(&self.root_node, &self.active_closures);
+        node_ref::unbind_all(&self.root_node);
+        if let Some(parent) = self.root_node.parent_node() {
+            // the old root node and its closures are kept
+            // when it can not be replaced
+            if let Err(error) =
+                parent.replace_child(&created_node.node, &self.root_node)
+            {
+                crate::log!("Unable to replace the root node: {:?}", error);
+                return;
+            }
+        }
+        dispatch_mount(&created_node.node, &created_node.closures);
+        self.root_node = created_node.node;
+        self.active_closures = created_node.closures;
+    }
+
+    /// Remove the root node from the DOM and drop the closures of all the event listeners.
+    pub fn unmount(&mut self) {
+        for portal in self.portals.iter_mut() {
+            portal.unmount();
+        }
+        self.portals.clear();
+        dispatch_unmount(&self.root_node, &self.active_closures);
+        node_ref::unbind_all(&self.root_node);
+        if let Some(parent) = self.root_node.parent_node() {
+            parent
+                .remove_child(&self.root_node)
+                .expect("Could not remove the root node");
+        }
+        self.active_closures.clear();
     }
 
     /// Return the root node of your application, the highest ancestor of all other nodes in
@@ -359,6 +685,16 @@ where
     }
 }
 
+/// count the nodes of this node tree, including itself
+fn node_count<MSG>(node: &crate::Node<MSG>) -> usize {
+    match node.resolve() {
+        crate::Node::Element(element) => {
+            1 + element.children.iter().map(node_count).sum::<usize>()
+        }
+        _ => 1,
+    }
+}
+
 fn create_unique_identifier() -> u32 {
     let mut elem_unique_id =
         ELEM_UNIQUE_ID.lock().expect("Unable to obtain lock");
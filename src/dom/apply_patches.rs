@@ -1,6 +1,12 @@
 use super::{
+    node_ref::{
+        self,
+        DATA_SAURON_REF,
+    },
+    portal::DATA_SAURON_PORTAL_TARGET,
     ActiveClosure,
     CreatedNode,
+    PatchError,
 };
 use crate::{
     dom,
@@ -10,15 +16,14 @@ use crate::{
 use js_sys::Function;
 use std::{
     collections::{
+        BTreeSet,
         HashMap,
-        HashSet,
     },
     rc::Rc,
 };
 use wasm_bindgen::{
     closure::Closure,
     JsCast,
-    JsValue,
 };
 use web_sys::{
     Element,
@@ -27,15 +32,25 @@ use web_sys::{
     Text,
 };
 
+/// Marks the element which also has children added outside of the view,
+/// those are not taken as a drift of the DOM.
+pub(crate) const DATA_SAURON_UNMANAGED: &str = "data-sauron-unmanaged";
+
 /// Apply all of the patches to our old root node in order to create the new root node
 /// that we desire.
 /// This is usually used after diffing two virtual nodes.
+/// The old virtual node is the node which was last rendered in the root node, the parts of the
+/// DOM which drifted from it are rendered anew before the patches are applied.
+///
+/// Returns the closures which were added during the patching, together with the number of
+/// closures created for the drifted nodes, which are already added to the old closures.
 pub fn patch<N, DSP, MSG>(
     program: &Rc<DSP>,
     root_node: N,
+    old_vdom: &crate::Node<MSG>,
     old_closures: &mut ActiveClosure,
     patches: &[Patch<MSG>],
-) -> Result<ActiveClosure, JsValue>
+) -> Result<(ActiveClosure, usize), PatchError>
 where
     N: Into<Node>,
     MSG: 'static,
@@ -48,28 +63,97 @@ where
 
     // finding the nodes to be patched before hand, instead of calling it
     // in every patch loop.
-    let (element_nodes_to_patch, text_nodes_to_patch) =
-        find_nodes(root_node, patches);
+    let nodes_to_patch =
+        find_nodes(program, root_node, old_vdom, old_closures, patches)?;
 
     for patch in patches {
         let patch_node_idx = patch.node_idx();
 
-        if let Some(element) = element_nodes_to_patch.get(&patch_node_idx) {
+        if let Some(element) = nodes_to_patch.elements.get(&patch_node_idx) {
             let new_closures =
                 apply_element_patch(program, &element, old_closures, &patch)?;
             active_closures.extend(new_closures);
             continue;
         }
 
-        if let Some(text_node) = text_nodes_to_patch.get(&patch_node_idx) {
-            apply_text_patch(program, &text_node, &patch)?;
+        if let Some(text_node) = nodes_to_patch.texts.get(&patch_node_idx) {
+            let new_closures = apply_text_patch(program, &text_node, &patch)?;
+            active_closures.extend(new_closures);
             continue;
         }
 
-        unreachable!("Getting here means we didn't find the element or next node that we were supposed to patch.")
+        // Getting here means we didn't find the element or next node that we were supposed to patch.
+        return Err(PatchError::NodeNotFound(patch_node_idx));
+    }
+
+    Ok((active_closures, nodes_to_patch.reconciled_closures))
+}
+
+/// the elements and text nodes to be patched, keyed by their node index
+#[derive(Default)]
+struct NodesToPatch {
+    elements: HashMap<usize, Element>,
+    texts: HashMap<usize, Text>,
+    /// the number of closures created for the drifted nodes which are rendered anew
+    reconciled_closures: usize,
+}
+
+/// the node indexes of the patches
+struct PatchTargets {
+    nodes_to_find: BTreeSet<usize>,
+    /// the number of nodes in the subtree of each node in the old vdom,
+    /// indexed by the node index. This is computed once for each patching.
+    subtree_sizes: Vec<usize>,
+}
+
+impl PatchTargets {
+    fn new<MSG>(old_vdom: &crate::Node<MSG>, patches: &[Patch<MSG>]) -> Self {
+        let mut subtree_sizes = vec![];
+        count_subtree_sizes(old_vdom, &mut subtree_sizes);
+        PatchTargets {
+            nodes_to_find: patches
+                .iter()
+                .map(|patch| patch.node_idx())
+                .collect(),
+            subtree_sizes,
+        }
+    }
+
+    fn contains(&self, node_idx: usize) -> bool {
+        self.nodes_to_find.contains(&node_idx)
+    }
+
+    /// the node index right after the subtree of this node,
+    /// the node indexes of a subtree are contiguous, since they are arranged in depth first
+    fn subtree_end(&self, node_idx: usize) -> usize {
+        node_idx + self.subtree_sizes.get(node_idx).cloned().unwrap_or(1)
     }
 
-    Ok(active_closures)
+    /// checks whether there are nodes to be patched in the subtree of this node
+    fn in_subtree(&self, node_idx: usize) -> bool {
+        self.nodes_to_find
+            .range(node_idx..self.subtree_end(node_idx))
+            .next()
+            .is_some()
+    }
+}
+
+/// push the size of the subtree of this node and its descendants in depth first order,
+/// returns the size of the subtree of this node
+fn count_subtree_sizes<MSG>(
+    vnode: &crate::Node<MSG>,
+    subtree_sizes: &mut Vec<usize>,
+) -> usize {
+    let node_idx = subtree_sizes.len();
+    subtree_sizes.push(1);
+    if let crate::Node::Element(element) = vnode.resolve() {
+        subtree_sizes[node_idx] += element
+            .children
+            .iter()
+            .map(|child| count_subtree_sizes(child, subtree_sizes))
+            .sum::<usize>();
+    }
+    subtree_sizes[node_idx]
 }
 
 /// find the nodes to be patched
@@ -80,111 +164,317 @@ where
 /// Instead of finding the nodes each time in the patching process.
 /// We find them before hand so as not to keep calling this function for each and every element to
 /// be patched.
-fn find_nodes<MSG>(
+///
+/// The DOM is walked together with the old virtual node, so the nodes which no longer
+/// match their virtual node (ie: modified by third-party scripts) are detected and rendered anew,
+/// otherwise the patches would land on the wrong nodes.
+/// Only the subtrees which contain nodes to be patched are walked.
+fn find_nodes<DSP, MSG>(
+    program: &Rc<DSP>,
     root_node: Node,
+    old_vdom: &crate::Node<MSG>,
+    old_closures: &mut ActiveClosure,
     patches: &[Patch<MSG>],
-) -> (HashMap<usize, Element>, HashMap<usize, Text>) {
+) -> Result<NodesToPatch, PatchError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + 'static,
+{
     let mut cur_node_idx = 0;
-    let mut nodes_to_find = HashSet::new();
-
-    for patch in patches {
-        nodes_to_find.insert(patch.node_idx());
-    }
-
-    find_nodes_recursive(root_node, &mut cur_node_idx, &nodes_to_find)
+    let targets = PatchTargets::new(old_vdom, patches);
+    let mut nodes_to_patch = NodesToPatch::default();
+
+    find_nodes_recursive(
+        program,
+        root_node,
+        old_vdom,
+        &mut cur_node_idx,
+        &targets,
+        old_closures,
+        &mut nodes_to_patch,
+    )?;
+    Ok(nodes_to_patch)
 }
 
-fn find_nodes_recursive(
-    root_node: Node,
+fn find_nodes_recursive<DSP, MSG>(
+    program: &Rc<DSP>,
+    node: Node,
+    vnode: &crate::Node<MSG>,
     cur_node_idx: &mut usize,
-    nodes_to_find: &HashSet<usize>,
-) -> (HashMap<usize, Element>, HashMap<usize, Text>) {
-
-    let mut element_nodes_to_patch = HashMap::new();
-    let mut text_nodes_to_patch = HashMap::new();
-
-    // We use child_nodes() instead of children() because children() ignores text nodes
-    let children = root_node.child_nodes();
-    let child_node_count = children.length();
+    targets: &PatchTargets,
+    old_closures: &mut ActiveClosure,
+    nodes_to_patch: &mut NodesToPatch,
+) -> Result<(), PatchError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + 'static,
+{
+    let vnode = vnode.resolve();
+    if !targets.in_subtree(*cur_node_idx) {
+        *cur_node_idx = targets.subtree_end(*cur_node_idx);
+        return Ok(());
+    }
+    let (node, children) = match find_drift(&node, vnode) {
+        Ok(children) => (node, children),
+        Err(drift) => {
+            crate::log!(
+                "The DOM drifted at node {}: {}, rendering it anew",
+                cur_node_idx,
+                drift
+            );
+            let node = reconcile(
+                program,
+                &node,
+                vnode,
+                *cur_node_idx,
+                old_closures,
+                &mut nodes_to_patch.reconciled_closures,
+            )?;
+            let children = patchable_children(&node);
+            (node, children)
+        }
+    };
 
-    // If the root node matches, mark it for patching
-    if nodes_to_find.get(&cur_node_idx).is_some() {
-        match root_node.node_type() {
+    // If the node matches, mark it for patching
+    if targets.contains(*cur_node_idx) {
+        match node.node_type() {
             Node::ELEMENT_NODE => {
-                element_nodes_to_patch
-                    .insert(*cur_node_idx, root_node.unchecked_into());
+                nodes_to_patch
+                    .elements
+                    .insert(*cur_node_idx, node.clone().unchecked_into());
             }
             Node::TEXT_NODE => {
-                text_nodes_to_patch
-                    .insert(*cur_node_idx, root_node.unchecked_into());
+                nodes_to_patch
+                    .texts
+                    .insert(*cur_node_idx, node.clone().unchecked_into());
+            }
+            other => {
+                return Err(PatchError::UnsupportedNodeType(
+                    *cur_node_idx,
+                    other,
+                ))
             }
-            other => unimplemented!("Unsupported root node type: {}", other),
         }
     }
 
     *cur_node_idx += 1;
 
-    for i in 0..child_node_count {
-        let child_node = children.item(i).expect("Expecting a child node");
+    if let crate::Node::Element(element) = vnode {
+        // the children of a child component host is patched by the child program
+        if crate::component::child::is_host(&node) {
+            return Ok(());
+        }
+        for (child_node, child_vnode) in
+            children.into_iter().zip(element.children.iter())
+        {
+            find_nodes_recursive(
+                program,
+                child_node,
+                child_vnode,
+                cur_node_idx,
+                targets,
+                old_closures,
+                nodes_to_patch,
+            )?;
+        }
+    }
 
-        match child_node.node_type() {
-            Node::ELEMENT_NODE => {
-                let child_to_patch = find_nodes_recursive(
-                    child_node,
-                    cur_node_idx,
-                    nodes_to_find,
-                );
-
-                element_nodes_to_patch.extend(child_to_patch.0);
-                text_nodes_to_patch.extend(child_to_patch.1);
-            }
-            Node::TEXT_NODE => {
-                if nodes_to_find.get(&cur_node_idx).is_some() {
-                    text_nodes_to_patch
-                        .insert(*cur_node_idx, child_node.unchecked_into());
-                }
+    Ok(())
+}
 
-                *cur_node_idx += 1;
+/// the element and text children of this node.
+///
+/// At this time we do not support user entered comment nodes, so if we see a comment
+/// then it was a delimiter created by virtual-dom-rs in order to ensure that two
+/// neighboring text nodes did not get merged into one by the browser. So we skip
+/// over this virtual-dom-rs generated comment node.
+/// The other node types are not created from the vdom and are also skipped.
+fn patchable_children(node: &Node) -> Vec<Node> {
+    // We use child_nodes() instead of children() because children() ignores text nodes
+    let children = node.child_nodes();
+    (0..children.length())
+        .filter_map(|i| children.item(i))
+        .filter(|child| {
+            child.node_type() == Node::ELEMENT_NODE
+                || child.node_type() == Node::TEXT_NODE
+        })
+        .collect()
+}
+
+/// Describes how this DOM node drifted from its virtual node,
+/// otherwise returns the patchable children of the node.
+/// The text content of the nodes are not compared since they are replaced by the patches anyway.
+///
+/// The extra children of the elements which are also modified imperatively, through a
+/// node ref, an `on_mount` listener or marked with `data-sauron-unmanaged`, are not
+/// taken as a drift. So are the portal contents in the elements which are portal targets.
+fn find_drift<MSG>(
+    node: &Node,
+    vnode: &crate::Node<MSG>,
+) -> Result<Vec<Node>, String> {
+    match vnode {
+        crate::Node::Element(element) => {
+            if node.node_type() != Node::ELEMENT_NODE
+                || !node.node_name().eq_ignore_ascii_case(element.tag)
+            {
+                return Err(format!(
+                    "expecting a <{}> element, found {}",
+                    element.tag,
+                    node.node_name()
+                ));
+            }
+            if crate::component::child::is_host(node) {
+                return Ok(vec![]);
             }
-            Node::COMMENT_NODE => {
-                // At this time we do not support user entered comment nodes, so if we see a comment
-                // then it was a delimiter created by virtual-dom-rs in order to ensure that two
-                // neighboring text nodes did not get merged into one by the browser. So we skip
-                // over this virtual-dom-rs generated comment node.
+            let children = patchable_children(node);
+            let portal_target = node
+                .unchecked_ref::<Element>()
+                .has_attribute(DATA_SAURON_PORTAL_TARGET);
+            let unmanaged = portal_target
+                || element.attrs.iter().any(|attr| {
+                    attr.name == DATA_SAURON_REF
+                        || attr.name == DATA_SAURON_UNMANAGED
+                        || (attr.name == dom::MOUNT_EVENT && attr.is_event())
+                });
+            if unmanaged && children.len() >= element.children.len() {
+                return Ok(children);
             }
-            _other => {
-                // Ignoring unsupported child node type
-                // TODO: What do we do with this situation? Log a warning?
+            if children.len() != element.children.len() {
+                Err(format!(
+                    "expecting {} children in <{}>, found {}",
+                    element.children.len(),
+                    element.tag,
+                    children.len()
+                ))
+            } else {
+                Ok(children)
             }
         }
+        crate::Node::Text(_) if node.node_type() != Node::TEXT_NODE => {
+            Err(format!("expecting a text node, found {}", node.node_name()))
+        }
+        _ => Ok(vec![]),
     }
+}
 
-    (element_nodes_to_patch, text_nodes_to_patch)
+/// Replace the drifted node with a node created from its virtual node.
+/// The closures of the drifted node and its descendants are dropped and the closures
+/// of the new node are added to the old closures, so they can be removed by the patches.
+/// The number of the closures created for the new node is added to `reconciled_closures`.
+fn reconcile<DSP, MSG>(
+    program: &Rc<DSP>,
+    node: &Node,
+    vnode: &crate::Node<MSG>,
+    node_idx: usize,
+    old_closures: &mut ActiveClosure,
+    reconciled_closures: &mut usize,
+) -> Result<Node, PatchError>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + 'static,
+{
+    let parent = node
+        .parent_node()
+        .ok_or(PatchError::NodeNotFound(node_idx))?;
+    let created_node =
+        CreatedNode::<Node>::create_dom_node::<DSP, MSG>(program, vnode);
+    dom::dispatch_unmount(node, old_closures);
+    node_ref::unbind_all(node);
+    remove_all_event_listeners(node, old_closures)?;
+    parent.replace_child(&created_node.node, node)?;
+    dom::dispatch_mount(&created_node.node, &created_node.closures);
+    *reconciled_closures += created_node
+        .closures
+        .values()
+        .map(|closures| closures.len())
+        .sum::<usize>();
+    old_closures.extend(created_node.closures);
+    Ok(created_node.node)
+}
+
+/// remove the event listeners of this node and all of its descendants,
+/// the elements without closures are skipped since the DOM may have been modified by
+/// something else.
+fn remove_all_event_listeners(
+    node: &Node,
+    old_closures: &mut ActiveClosure,
+) -> Result<(), PatchError> {
+    if node.node_type() != Node::ELEMENT_NODE {
+        return Ok(());
+    }
+    let element: &Element = node.unchecked_ref();
+    let mut elements = vec![element.clone()];
+    let descendants = element
+        .query_selector_all(&format!("[{}]", super::DATA_SAURON_VDOM_ID))?;
+    for i in 0..descendants.length() {
+        if let Some(descendant) = descendants.item(i) {
+            elements.push(descendant.unchecked_into());
+        }
+    }
+    for element in elements {
+        let has_closures = element
+            .get_attribute(super::DATA_SAURON_VDOM_ID)
+            .and_then(|vdom_id| vdom_id.parse::<u32>().ok())
+            .map(|vdom_id| old_closures.contains_key(&vdom_id))
+            .unwrap_or(false);
+        if has_closures {
+            remove_event_listeners(&element, old_closures)?;
+        }
+    }
+    Ok(())
 }
 
 /// remove all the event listeners for this node
 fn remove_event_listeners(
     node: &Element,
     old_closures: &mut ActiveClosure,
-) -> Result<(), JsValue> {
+) -> Result<(), PatchError> {
     // TODO: there should be a better way to get the node-id back
     // without having to read from the actual dom node element
     if let Some(vdom_id_str) = node.get_attribute(super::DATA_SAURON_VDOM_ID) {
         let vdom_id = vdom_id_str
             .parse::<u32>()
-            .expect("unable to parse sauron_vdom-id");
+            .map_err(|_| PatchError::InvalidVdomId(vdom_id_str.clone()))?;
+        // remove closure active_closure in dom_updater to free up memory
         let old_closure = old_closures
-            .get(&vdom_id)
-            .expect("There is no marked with that vdom_id");
+            .remove(&vdom_id)
+            .ok_or(PatchError::MissingClosures(vdom_id))?;
         for (event, oc) in old_closure.iter() {
             let func: &Function = oc.as_ref().unchecked_ref();
             node.remove_event_listener_with_callback(event, func)?;
         }
+    }
+    Ok(())
+}
 
-        // remove closure active_closure in dom_updater to free up memory
-        old_closures
-            .remove(&vdom_id)
-            .expect("Unable to remove old closure");
+/// remove only the event listeners with these names from this node,
+/// the closures of the other events stay attached to the node.
+/// The vdom id of the node is removed when it has no more closures, so the node
+/// is not looked up in the closures when it is removed later on.
+fn remove_named_event_listeners(
+    node: &Element,
+    events: &[&'static str],
+    old_closures: &mut ActiveClosure,
+) -> Result<(), PatchError> {
+    if let Some(vdom_id_str) = node.get_attribute(super::DATA_SAURON_VDOM_ID) {
+        let vdom_id = vdom_id_str
+            .parse::<u32>()
+            .map_err(|_| PatchError::InvalidVdomId(vdom_id_str.clone()))?;
+        let closures = old_closures
+            .get_mut(&vdom_id)
+            .ok_or(PatchError::MissingClosures(vdom_id))?;
+        let (removed, kept) = closures
+            .drain(..)
+            .partition(|(event, _)| events.contains(event));
+        *closures = kept;
+        for (event, oc) in removed.iter() {
+            let func: &Function = oc.as_ref().unchecked_ref();
+            node.remove_event_listener_with_callback(event, func)?;
+        }
+        if closures.is_empty() {
+            old_closures.remove(&vdom_id);
+            node.remove_attribute(super::DATA_SAURON_VDOM_ID)?;
+        }
     }
     Ok(())
 }
@@ -194,22 +484,31 @@ fn apply_element_patch<DSP, MSG>(
     node: &Element,
     old_closures: &mut ActiveClosure,
     patch: &Patch<MSG>,
-) -> Result<ActiveClosure, JsValue>
+) -> Result<ActiveClosure, PatchError>
 where
     MSG: 'static,
     DSP: Dispatch<MSG> + 'static,
 {
-    let mut active_closures = ActiveClosure::new();
+    let active_closures = ActiveClosure::new();
     match patch {
         Patch::AddAttributes(_node_idx, attributes) => {
             for attr in attributes.iter() {
+                if attr.name == DATA_SAURON_REF {
+                    node_ref::unbind(node);
+                }
                 node.set_attribute(attr.name, &attr.value.to_string())?;
+                if attr.name == DATA_SAURON_REF {
+                    node_ref::bind(node);
+                }
             }
 
             Ok(active_closures)
         }
         Patch::RemoveAttributes(_node_idx, attributes) => {
             for attrib_name in attributes.iter() {
+                if *attrib_name == DATA_SAURON_REF {
+                    node_ref::unbind(node);
+                }
                 node.remove_attribute(attrib_name)?;
             }
 
@@ -218,26 +517,40 @@ where
 
         // TODO: Shall we also remove the listener first?
         Patch::AddEventListener(node_idx, events) => {
+            // the closures are keyed with the vdom id of the element,
+            // so they can be found again when the element is removed
+            let vdom_id = match node
+                .get_attribute(super::DATA_SAURON_VDOM_ID)
+                .and_then(|vdom_id| vdom_id.parse::<u32>().ok())
+            {
+                Some(vdom_id) => vdom_id,
+                None => {
+                    let vdom_id = super::create_unique_identifier();
+                    node.set_attribute(
+                        super::DATA_SAURON_VDOM_ID,
+                        &vdom_id.to_string(),
+                    )?;
+                    vdom_id
+                }
+            };
             for event in events.iter() {
-                let callback =
-                    event.value.get_callback().expect("expecting a callback");
+                let callback = event.value.get_callback().ok_or(
+                    PatchError::InvalidPatch(*node_idx, "expecting a callback"),
+                )?;
                 let closure_wrap: Closure<dyn FnMut(Event)> =
                     dom::create_closure_wrap(program, callback);
                 let func: &Function = closure_wrap.as_ref().unchecked_ref();
                 node.add_event_listener_with_callback(event.name, func)?;
-                let node_id = *node_idx as u32;
-                if let Some(closure) = active_closures.get_mut(&node_id) {
-                    closure.push((event.name, closure_wrap));
-                } else {
-                    active_closures
-                        .insert(node_id, vec![(event.name, closure_wrap)]);
-                }
+                old_closures
+                    .entry(vdom_id)
+                    .or_default()
+                    .push((event.name, closure_wrap));
             }
 
             Ok(active_closures)
         }
-        Patch::RemoveEventListener(_node_idx, _events) => {
-            remove_event_listeners(node, old_closures)?;
+        Patch::RemoveEventListener(_node_idx, events) => {
+            remove_named_event_listeners(node, events, old_closures)?;
             Ok(active_closures)
         }
         // THis also removes the associated closures and event listeners to the node being replaced
@@ -245,12 +558,18 @@ where
         //
         // Note and TODO: This doesn't free the closure and event listeners
         // of the children of this node
+        //
+        // The child components and the node refs of the new node are attached
+        // only once it is inserted in the document, after the old node is cleaned up.
         Patch::Replace(_node_idx, new_node) => {
             let created_node = CreatedNode::<Node>::create_dom_node::<DSP, MSG>(
                 program, new_node,
             );
+            dom::dispatch_unmount(node, old_closures);
+            node_ref::unbind_all(node);
             remove_event_listeners(&node, old_closures)?;
             node.replace_with_with_node_1(&created_node.node)?;
+            dom::dispatch_mount(&created_node.node, &created_node.closures);
             Ok(created_node.closures)
         }
         // This also removes the associated closures and event listener to the truncated chilren
@@ -263,27 +582,42 @@ where
         // them). However, we keep a list of our active closure in dom::ActiveClosure
         // whic needs to be manually remove by traversing of getting the
         // sauron VDOM id for each and remove is from the ActiveClosure hashmap
-        Patch::TruncateChildren(_node_idx, num_children_remaining) => {
-            let children = node.child_nodes();
-            let child_count = children.length();
-
+        Patch::TruncateChildren(node_idx, num_children_remaining) => {
             // We skip over any separators that we placed between two text nodes
             //   -> `<!--ptns-->`
             //  and trim all children that come after our new desired `num_children_remaining`
-            //let mut non_separator_children_found = 0;
-
-            let to_be_remove_len =
-                child_count as usize - num_children_remaining;
-            for _index in 0..to_be_remove_len {
-                let last_child = node.last_child().expect("No more last child");
-                let last_element: &Element = last_child.unchecked_ref();
-                remove_event_listeners(last_element, old_closures)?;
-                // Do not remove comment node
-                if last_child.node_type() == Node::COMMENT_NODE {
-                    continue;
+            let to_be_removed = patchable_children(node)
+                .len()
+                .checked_sub(*num_children_remaining)
+                .ok_or(PatchError::InvalidPatch(
+                    *node_idx,
+                    "there are less children than the remaining children",
+                ))?;
+            let mut removed = 0;
+            while removed < to_be_removed {
+                let last_child = node
+                    .last_child()
+                    .ok_or(PatchError::NodeNotFound(*node_idx))?;
+                match last_child.node_type() {
+                    Node::ELEMENT_NODE => {
+                        let last_element: &Element = last_child.unchecked_ref();
+                        dom::dispatch_unmount(&last_child, old_closures);
+                        node_ref::unbind_all(&last_child);
+                        remove_event_listeners(last_element, old_closures)?;
+                        removed += 1;
+                    }
+                    Node::TEXT_NODE => removed += 1,
+                    // the separators are removed together with the text nodes
+                    _ => (),
                 }
-                node.remove_child(&last_child)
-                    .expect("Unable to remove last child");
+                node.remove_child(&last_child)?;
+            }
+            // the separator of the last removed text node is no longer needed
+            while let Some(last_child) = node.last_child() {
+                if last_child.node_type() != Node::COMMENT_NODE {
+                    break;
+                }
+                node.remove_child(&last_child)?;
             }
 
             Ok(active_closures)
@@ -297,13 +631,17 @@ where
                     MSG,
                 >(program, &new_node);
                 parent.append_child(&created_node.node)?;
+                dom::dispatch_mount(&created_node.node, &created_node.closures);
                 active_closures.extend(created_node.closures);
             }
 
             Ok(active_closures)
         }
-        Patch::ChangeText(_node_idx, _new_node) => {
-            unreachable!("Elements should not receive ChangeText patches.")
+        Patch::ChangeText(node_idx, _new_node) => {
+            Err(PatchError::InvalidPatch(
+                *node_idx,
+                "Elements should not receive ChangeText patches.",
+            ))
         }
     }
 }
@@ -312,7 +650,7 @@ fn apply_text_patch<DSP, MSG>(
     program: &Rc<DSP>,
     node: &Text,
     patch: &Patch<MSG>,
-) -> Result<(), JsValue>
+) -> Result<ActiveClosure, PatchError>
 where
     MSG: 'static,
     DSP: Dispatch<MSG> + 'static,
@@ -320,19 +658,21 @@ where
     match patch {
         Patch::ChangeText(_node_idx, new_node) => {
             node.set_node_value(Some(&new_node.text));
+            Ok(ActiveClosure::new())
         }
         Patch::Replace(_node_idx, new_node) => {
             let created_node = CreatedNode::<Node>::create_dom_node::<DSP, MSG>(
                 program, new_node,
             );
             node.replace_with_with_node_1(&created_node.node)?;
+            dom::dispatch_mount(&created_node.node, &created_node.closures);
+            Ok(created_node.closures)
         }
-        _other => {
-            unreachable!(
-                "Text nodes should only receive ChangeText or Replace patches."
-            )
+        other => {
+            Err(PatchError::InvalidPatch(
+                other.node_idx(),
+                "Text nodes should only receive ChangeText or Replace patches.",
+            ))
         }
-    };
-
-    Ok(())
+    }
 }
@@ -0,0 +1,52 @@
+use std::fmt;
+use wasm_bindgen::JsValue;
+
+/// The errors which can happen when the patches are applied to the DOM.
+/// This usually means the DOM has been modified outside of the program,
+/// ie: by a browser extension.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchError {
+    /// the node at this index of the view is not found in the DOM
+    NodeNotFound(usize),
+    /// the DOM node at this index has a node type which can not be patched
+    UnsupportedNodeType(usize, u16),
+    /// the patch can not be applied to the node at this index
+    InvalidPatch(usize, &'static str),
+    /// the `data-sauron-vdom-id` attribute of an element is not a valid id
+    InvalidVdomId(String),
+    /// there are no event listener closures for the element with this vdom id
+    MissingClosures(u32),
+    /// an error thrown by the DOM api
+    Js(JsValue),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::NodeNotFound(node_idx) => {
+                write!(f, "node {} is not found in the DOM", node_idx)
+            }
+            PatchError::UnsupportedNodeType(node_idx, node_type) => write!(
+                f,
+                "node {} has an unsupported node type: {}",
+                node_idx, node_type
+            ),
+            PatchError::InvalidPatch(node_idx, reason) => {
+                write!(f, "invalid patch for node {}: {}", node_idx, reason)
+            }
+            PatchError::InvalidVdomId(vdom_id) => {
+                write!(f, "invalid vdom id: {}", vdom_id)
+            }
+            PatchError::MissingClosures(vdom_id) => {
+                write!(f, "there are no closures for vdom id: {}", vdom_id)
+            }
+            PatchError::Js(js_value) => write!(f, "dom error: {:?}", js_value),
+        }
+    }
+}
+
+impl From<JsValue> for PatchError {
+    fn from(js_value: JsValue) -> Self {
+        PatchError::Js(js_value)
+    }
+}
@@ -0,0 +1,209 @@
+//! NodeRef gives the component code access to the actual DOM element
+//! that was created for a virtual node.
+//!
+//! The element is marked with a `data-sauron-ref` attribute which holds the unique id of the
+//! NodeRef. The DomUpdater binds the element to the NodeRef when the element is inserted in
+//! the document and unbinds it when the element is removed from the DOM.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    rc::{
+        Rc,
+        Weak,
+    },
+};
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Element,
+    Node,
+};
+
+pub(crate) const DATA_SAURON_REF: &str = "data-sauron-ref";
+
+thread_local! {
+    static NODE_REF_UNIQUE_ID: RefCell<u32> = RefCell::new(0);
+    static NODE_REFS: RefCell<HashMap<u32, Weak<NodeRefInner>>> = RefCell::new(HashMap::new());
+}
+
+/// A handle to the real DOM element of a virtual node.
+///
+/// Keep the NodeRef in the component and attach it to an element with
+/// `html::attributes::node_ref`.
+/// ```ignore
+///     canvas(vec![node_ref(&self.canvas)], vec![])
+/// ```
+/// The element is available with `get` or `cast` once the view is mounted, and becomes `None`
+/// again when the element is removed.
+#[derive(Clone)]
+pub struct NodeRef(Rc<NodeRefInner>);
+
+type Listener = Box<dyn Fn(Option<&Element>)>;
+
+struct NodeRefInner {
+    id: u32,
+    element: RefCell<Option<Element>>,
+    listener: Option<Listener>,
+}
+
+impl NodeRef {
+    /// Create a NodeRef which is not yet bound to any element,
+    /// attach it to an element in the view with `html::attributes::node_ref`.
+    pub fn new() -> Self {
+        Self::create(None)
+    }
+
+    /// Create a NodeRef which calls the listener with the element when it is mounted
+    /// and with `None` when the element is unmounted.
+    pub fn with_listener<F>(listener: F) -> Self
+    where
+        F: Fn(Option<&Element>) + 'static,
+    {
+        Self::create(Some(Box::new(listener)))
+    }
+
+    fn create(listener: Option<Listener>) -> Self {
+        let id = NODE_REF_UNIQUE_ID.with(|unique_id| {
+            let mut unique_id = unique_id.borrow_mut();
+            *unique_id += 1;
+            *unique_id
+        });
+        let inner = Rc::new(NodeRefInner {
+            id,
+            element: RefCell::new(None),
+            listener,
+        });
+        NODE_REFS.with(|node_refs| {
+            node_refs.borrow_mut().insert(id, Rc::downgrade(&inner))
+        });
+        NodeRef(inner)
+    }
+
+    /// the unique id of this NodeRef, this is the value of the `data-sauron-ref`
+    /// attribute of the bound element
+    pub fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    /// the element that is currently bound to this NodeRef
+    pub fn get(&self) -> Option<Element> {
+        self.0.element.borrow().clone()
+    }
+
+    /// the element bound to this NodeRef casted to a more specific type.
+    /// ie: `web_sys::HtmlCanvasElement`
+    pub fn cast<T: JsCast>(&self) -> Option<T> {
+        self.get().and_then(|element| element.dyn_into().ok())
+    }
+
+    fn set(&self, element: Option<&Element>) {
+        *self.0.element.borrow_mut() = element.cloned();
+        if let Some(ref listener) = self.0.listener {
+            listener(element);
+        }
+    }
+
+    fn find(id: u32) -> Option<NodeRef> {
+        NODE_REFS.with(|node_refs| {
+            node_refs
+                .borrow()
+                .get(&id)
+                .and_then(|inner| inner.upgrade())
+                .map(NodeRef)
+        })
+    }
+}
+
+impl Default for NodeRef {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for NodeRef {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for NodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "NodeRef({})", self.0.id)
+    }
+}
+
+impl Drop for NodeRefInner {
+    fn drop(&mut self) {
+        // the thread local may already be destroyed at this point
+        let _ = NODE_REFS
+            .try_with(|node_refs| node_refs.borrow_mut().remove(&self.id));
+    }
+}
+
+fn node_ref_of(element: &Element) -> Option<NodeRef> {
+    element
+        .get_attribute(DATA_SAURON_REF)
+        .and_then(|id| id.parse::<u32>().ok())
+        .and_then(NodeRef::find)
+}
+
+/// bind this element to the NodeRef that is marked in its attribute.
+/// This is a no op if the element is already bound to its NodeRef.
+pub(crate) fn bind(element: &Element) {
+    if let Some(node_ref) = node_ref_of(element) {
+        if node_ref.get().as_ref() != Some(element) {
+            node_ref.set(Some(element));
+        }
+    }
+}
+
+/// bind this node and all of its descendants to their NodeRefs,
+/// This is called after the node is inserted in the document, so the element is already
+/// mounted when the listener of the NodeRef is called, ie: for measuring its layout.
+/// The node is skipped when it is not in the document yet,
+/// it is bound once its ancestor is inserted in the document.
+pub(crate) fn bind_all(node: &Node) {
+    if node.node_type() != Node::ELEMENT_NODE || !node.is_connected() {
+        return;
+    }
+    let element: &Element = node.unchecked_ref();
+    bind(element);
+    if let Ok(descendants) =
+        element.query_selector_all(&format!("[{}]", DATA_SAURON_REF))
+    {
+        for i in 0..descendants.length() {
+            if let Some(descendant) = descendants.item(i) {
+                bind(descendant.unchecked_ref());
+            }
+        }
+    }
+}
+
+/// unbind this element from its NodeRef.
+/// This is a no op if the NodeRef has already been bound to a different element.
+pub(crate) fn unbind(element: &Element) {
+    if let Some(node_ref) = node_ref_of(element) {
+        if node_ref.get().as_ref() == Some(element) {
+            node_ref.set(None);
+        }
+    }
+}
+
+/// unbind this node and all of its descendants from their NodeRefs,
+/// This is called before the node is removed from the DOM.
+pub(crate) fn unbind_all(node: &Node) {
+    if node.node_type() != Node::ELEMENT_NODE {
+        return;
+    }
+    let element: &Element = node.unchecked_ref();
+    unbind(element);
+    if let Ok(descendants) =
+        element.query_selector_all(&format!("[{}]", DATA_SAURON_REF))
+    {
+        for i in 0..descendants.length() {
+            if let Some(descendant) = descendants.item(i) {
+                unbind(descendant.unchecked_ref());
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Portals render a part of the view under a different DOM element than the root node of
+//! the program, ie: modals and tooltips which needs to escape the `overflow: hidden` of their
+//! containers.
+//!
+//! The portal is a `template` element in the view which holds the target selector and
+//! the content of the portal. The content is taken out of the view before it is diffed and is
+//! managed by a DomUpdater of its own which is mounted to the target, using the same program
+//! so the events of the content are still dispatched to the owning program.
+use super::{
+    DomUpdater,
+    PatchError,
+};
+use crate::Dispatch;
+use std::rc::Rc;
+use web_sys::Element;
+
+pub(crate) const DATA_SAURON_PORTAL: &str = "data-sauron-portal";
+/// Marks the target element with the number of the portals mounted in it,
+/// the portal contents in the target are not taken as a drift of the DOM
+/// when the target is also in a view.
+pub(super) const DATA_SAURON_PORTAL_TARGET: &str = "data-sauron-portal-target";
+
+/// The content of a portal which is mounted in the target
+pub(super) struct Portal<DSP, MSG>
+where
+    MSG: 'static,
+{
+    target: String,
+    /// None when the target is not found, nothing is rendered for the portal then
+    dom_updater: Option<DomUpdater<DSP, MSG>>,
+    target_element: Option<Element>,
+}
+
+impl<DSP, MSG> Portal<DSP, MSG>
+where
+    MSG: 'static,
+    DSP: Dispatch<MSG> + 'static,
+{
+    fn new(target: String, content: crate::Node<MSG>) -> Self {
+        let target_element = match crate::document().query_selector(&target) {
+            Ok(Some(target_element)) => Some(target_element),
+            Ok(None) => {
+                crate::log!("Unable to find the portal target: {}", target);
+                None
+            }
+            Err(error) => {
+                crate::log!(
+                    "Invalid portal target selector {}: {:?}",
+                    target,
+                    error
+                );
+                None
+            }
+        };
+        Portal {
+            dom_updater: target_element
+                .as_ref()
+                .map(|target_element| DomUpdater::new(content, target_element)),
+            target_element,
+            target,
+        }
+    }
+
+    /// create the portals of these contents without mounting them yet
+    pub(super) fn create_all(
+        contents: Vec<(String, crate::Node<MSG>)>,
+    ) -> Vec<Self> {
+        contents
+            .into_iter()
+            .map(|(target, content)| Portal::new(target, content))
+            .collect()
+    }
+
+    pub(super) fn mount(&mut self, program: &Rc<DSP>) {
+        if let Some(dom_updater) = self.dom_updater.as_mut() {
+            dom_updater.append_to_mount(program);
+        }
+        if let Some(target_element) = self.target_element.as_ref() {
+            add_portal_count(target_element, 1);
+        }
+    }
+
+    pub(super) fn unmount(&mut self) {
+        if let Some(dom_updater) = self.dom_updater.as_mut() {
+            dom_updater.unmount();
+        }
+        if let Some(target_element) = self.target_element.as_ref() {
+            add_portal_count(target_element, -1);
+        }
+    }
+
+    /// Update the portals with the new contents.
+    /// The portals are matched by their order in the view, a portal is recreated
+    /// when its target changed or when its target was not found before.
+    /// All of the portals are updated, the first patch error of the portals is returned.
+    pub(super) fn update_all(
+        portals: &mut Vec<Self>,
+        program: &Rc<DSP>,
+        contents: Vec<(String, crate::Node<MSG>)>,
+    ) -> Result<(), PatchError> {
+        let mut patch_error = None;
+        let mut old_portals = portals.drain(..);
+        let mut new_portals = Vec::with_capacity(contents.len());
+        for (target, content) in contents {
+            match old_portals.next() {
+                Some(Portal {
+                    target: old_target,
+                    dom_updater: Some(mut dom_updater),
+                    target_element,
+                }) if old_target == target => {
+                    if let Err(error) =
+                        dom_updater.try_update_dom(program, content)
+                    {
+                        patch_error.get_or_insert(error);
+                    }
+                    new_portals.push(Portal {
+                        target,
+                        dom_updater: Some(dom_updater),
+                        target_element,
+                    });
+                }
+                old_portal => {
+                    if let Some(mut old_portal) = old_portal {
+                        old_portal.unmount();
+                    }
+                    let mut portal = Portal::new(target, content);
+                    portal.mount(program);
+                    new_portals.push(portal);
+                }
+            }
+        }
+        for mut old_portal in old_portals {
+            old_portal.unmount();
+        }
+        *portals = new_portals;
+        match patch_error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// update the number of the portals mounted in the target,
+/// the mark is removed when there are no more portals in the target
+fn add_portal_count(target_element: &Element, count: i32) {
+    let portal_count = target_element
+        .get_attribute(DATA_SAURON_PORTAL_TARGET)
+        .and_then(|portal_count| portal_count.parse::<i32>().ok())
+        .unwrap_or(0)
+        + count;
+    let marked = if portal_count > 0 {
+        target_element
+            .set_attribute(DATA_SAURON_PORTAL_TARGET, &portal_count.to_string())
+    } else {
+        target_element.remove_attribute(DATA_SAURON_PORTAL_TARGET)
+    };
+    if let Err(error) = marked {
+        crate::log!("Unable to mark the portal target: {:?}", error);
+    }
+}
+
+/// Take out the contents of the portals in this view, leaving only the empty portal elements.
+/// The contents of nested portals are taken out by the DomUpdater of the outer portal.
+pub(super) fn take_contents<MSG>(
+    node: &mut crate::Node<MSG>,
+) -> Vec<(String, crate::Node<MSG>)> {
+    let mut contents = vec![];
+    take_contents_recursive(node, &mut contents);
+    contents
+}
+
+fn take_contents_recursive<MSG>(
+    node: &mut crate::Node<MSG>,
+    contents: &mut Vec<(String, crate::Node<MSG>)>,
+) {
+    if let Some(element) = node.as_element() {
+        if let Some(target) = element.get_attr_value(DATA_SAURON_PORTAL) {
+            for content in element.children.drain(..) {
+                contents.push((target.to_string(), content));
+            }
+        } else {
+            for child in element.children.iter_mut() {
+                take_contents_recursive(child, contents);
+            }
+        }
+    }
+}
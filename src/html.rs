@@ -1,9 +1,17 @@
 use crate::{
+    dom::DATA_SAURON_PORTAL,
     Attribute,
     Node,
+    StatelessComponent,
+};
+pub use crate::component::{
+    catch_view,
+    component,
+    error_boundary,
 };
 pub use sauron_vdom::builder::{
     attr,
+    lazy,
     on,
     text,
 };
@@ -32,6 +40,36 @@ pub fn html_element_ns<MSG>(
     sauron_vdom::builder::element_ns(tag, Some(namespace), attrs, children)
 }
 
+/// Place a stateless component in the view,
+/// the component is rendered again only when its props changed.
+/// ```ignore
+///     ul(vec![], self.rows.iter().map(|row| stateless(row.clone())).collect())
+/// ```
+#[inline]
+pub fn stateless<COMP, MSG>(props: COMP) -> Node<MSG>
+where
+    COMP: StatelessComponent<MSG>,
+    MSG: 'static,
+{
+    lazy(props, COMP::view)
+}
+
+/// Render the content under the element found with the target selector instead of
+/// at this position of the view, ie: modals which are rendered under the `body`.
+/// The events of the content are still dispatched to the program of this view.
+/// Note: portals inside of lazy nodes are not supported.
+/// ```ignore
+///     div(vec![], vec![portal("body", div(vec![class("modal")], vec![text("Hello")]))])
+/// ```
+#[inline]
+pub fn portal<MSG>(target: &str, content: Node<MSG>) -> Node<MSG> {
+    html_element(
+        "template",
+        vec![attr(DATA_SAURON_PORTAL, target.to_string())],
+        vec![content],
+    )
+}
+
 macro_rules! declare_tags {
     ( $(
          $(#[$attr:meta])*
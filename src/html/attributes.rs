@@ -1,6 +1,13 @@
 //! https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes
 //!
-use crate::Attribute;
+use crate::{
+    dom::{
+        DATA_SAURON_REF,
+        DATA_SAURON_UNMANAGED,
+    },
+    Attribute,
+    NodeRef,
+};
 pub use sauron_vdom::builder::attr;
 use sauron_vdom::Value;
 
@@ -272,3 +279,19 @@ where
     }
     attributes
 }
+
+/// Bind the DOM element created for this node to the NodeRef,
+/// so the component can do imperative work on the actual element.
+/// ```ignore
+///     canvas(vec![node_ref(&self.canvas)], vec![])
+/// ```
+pub fn node_ref<MSG>(node_ref: &NodeRef) -> Attribute<MSG> {
+    attr(DATA_SAURON_REF, node_ref.id())
+}
+
+/// Mark the element which also has children added outside of the view,
+/// ie: by a third-party widget, so the extra children are kept when the view is patched.
+/// The elements with a `node_ref` or an `on_mount` listener are already treated this way.
+pub fn unmanaged_children<MSG>() -> Attribute<MSG> {
+    attr(DATA_SAURON_UNMANAGED, true)
+}
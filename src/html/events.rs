@@ -6,31 +6,85 @@ pub use sauron_vdom::{
         on_with_extractor,
     },
     event::{
+        AnimationEvent,
+        ClipboardEvent,
+        CompositionEvent,
         Coordinate,
+        DataTransferItem,
+        DeltaMode,
+        FocusEvent,
+        FormValues,
         InputEvent,
+        Key,
         KeyEvent,
+        MediaEvent,
         Modifier,
-        MouseEvent,
         MouseButton,
+        ScrollEvent,
+        Touch,
+        TouchEvent,
+        TransitionEvent,
     },
     Callback,
 };
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{
+    JsCast,
+    JsValue,
+};
+
+/// A mouse event, the related target is the DOM node the pointer came from or went to
+pub type MouseEvent = sauron_vdom::event::MouseEvent<web_sys::EventTarget>;
+/// A pointer event, the related target is the DOM node the pointer came from or went to
+pub type PointerEvent = sauron_vdom::event::PointerEvent<web_sys::EventTarget>;
+/// A drag event, the dropped files are the `File` handles of the browser
+pub type DragEvent =
+    sauron_vdom::event::DragEvent<web_sys::EventTarget, web_sys::File>;
+/// The data being dragged in a drag event
+pub type DataTransfer = sauron_vdom::event::DataTransfer<web_sys::File>;
+/// A wheel event, the related target is the DOM node the pointer came from or went to
+pub type WheelEvent = sauron_vdom::event::WheelEvent<web_sys::EventTarget>;
 
 pub mod mapper {
 
+    use super::{
+        DataTransfer,
+        DragEvent,
+        MouseEvent,
+        PointerEvent,
+        WheelEvent,
+    };
     use sauron_vdom::event::{
+        AnimationEvent,
+        ClipboardEvent,
+        CompositionEvent,
         Coordinate,
+        DataTransferItem,
+        DeltaMode,
+        FocusEvent,
+        FormValues,
         InputEvent,
         KeyEvent,
+        MediaEvent,
         Modifier,
-        MouseEvent,
         MouseButton,
+        ScrollEvent,
+        Touch,
+        TouchEvent,
+        TransitionEvent,
+    };
+    use wasm_bindgen::{
+        JsCast,
+        JsValue,
     };
-    use wasm_bindgen::JsCast;
     use web_sys::{
+        Element,
         EventTarget,
+        HtmlElement,
+        HtmlFormElement,
         HtmlInputElement,
+        HtmlMediaElement,
+        HtmlOptionElement,
+        HtmlSelectElement,
         HtmlTextAreaElement,
     };
 
@@ -44,6 +98,8 @@ pub mod mapper {
             movement_y: mouse.movement_y(),
             offset_x: mouse.offset_x(),
             offset_y: mouse.offset_y(),
+            page_x: mouse.page_x(),
+            page_y: mouse.page_y(),
             screen_x: mouse.screen_x(),
             screen_y: mouse.screen_y(),
             x: mouse.x(),
@@ -55,28 +111,236 @@ pub mod mapper {
             meta_key: mouse.meta_key(),
             shift_key: mouse.shift_key(),
         };
-        let buttons = match mouse.button(){
+        let button = match mouse.button(){
             0 => MouseButton::Left,
             1 => MouseButton::Middle,
-            2 => MouseButton::Left,
+            2 => MouseButton::Right,
             3 => MouseButton::WheelUp,
             4 => MouseButton::WheelDown,
             _ => Default::default(), // defaults to left
         };
-        let r#type = match &*event.0.type_(){
-            "click" => "click",
-            "mouseup" => "mouseup",
-            "mousedown" => "mousedown",
-            _e => panic!("unhandled event type: {}", _e),
-        };
         MouseEvent{
-            r#type,
+            r#type: event.0.type_(),
             coordinate,
             modifier,
-            buttons
+            button,
+            buttons: mouse.buttons(),
+            related_target: mouse.related_target(),
+        }
+    }
+
+    pub fn touch_event_mapper(event: crate::Event) -> TouchEvent {
+        let touch_event: &web_sys::TouchEvent =
+            event.0.dyn_ref().expect("Unable to cast to touch event");
+        let modifier = Modifier {
+            alt_key: touch_event.alt_key(),
+            ctrl_key: touch_event.ctrl_key(),
+            meta_key: touch_event.meta_key(),
+            shift_key: touch_event.shift_key(),
+        };
+        TouchEvent {
+            r#type: event.0.type_(),
+            touches: touch_list(&touch_event.touches()),
+            target_touches: touch_list(&touch_event.target_touches()),
+            changed_touches: touch_list(&touch_event.changed_touches()),
+            modifier,
+        }
+    }
+
+    fn touch_list(touch_list: &web_sys::TouchList) -> Vec<Touch> {
+        (0..touch_list.length())
+            .filter_map(|i| touch_list.item(i))
+            .map(|touch| {
+                Touch {
+                    identifier: touch.identifier(),
+                    client_x: touch.client_x(),
+                    client_y: touch.client_y(),
+                    page_x: touch.page_x(),
+                    page_y: touch.page_y(),
+                    screen_x: touch.screen_x(),
+                    screen_y: touch.screen_y(),
+                    radius_x: touch.radius_x(),
+                    radius_y: touch.radius_y(),
+                    rotation_angle: touch.rotation_angle(),
+                    force: touch.force(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn pointer_event_mapper(event: crate::Event) -> PointerEvent {
+        let pointer: web_sys::PointerEvent = event
+            .0
+            .dyn_ref::<web_sys::PointerEvent>()
+            .expect("Unable to cast to pointer event")
+            .clone();
+        PointerEvent {
+            mouse: mouse_event_mapper(event),
+            pointer_id: pointer.pointer_id(),
+            pointer_type: pointer.pointer_type(),
+            is_primary: pointer.is_primary(),
+            pressure: pointer.pressure(),
+            tangential_pressure: pointer.tangential_pressure(),
+            tilt_x: pointer.tilt_x(),
+            tilt_y: pointer.tilt_y(),
+            twist: pointer.twist(),
+            width: pointer.width(),
+            height: pointer.height(),
+        }
+    }
+
+    pub fn drag_event_mapper(event: crate::Event) -> DragEvent {
+        let drag: &web_sys::DragEvent =
+            event.0.dyn_ref().expect("Unable to cast to drag event");
+        let data_transfer = drag
+            .data_transfer()
+            .map(|data_transfer| map_data_transfer(&data_transfer))
+            .unwrap_or_default();
+        DragEvent {
+            mouse: mouse_event_mapper(event),
+            data_transfer,
+        }
+    }
+
+    fn map_data_transfer(
+        data_transfer: &web_sys::DataTransfer,
+    ) -> DataTransfer {
+        let types: Vec<String> = data_transfer
+            .types()
+            .iter()
+            .filter_map(|format| format.as_string())
+            .collect();
+        let item_list = data_transfer.items();
+        let items = (0..item_list.length())
+            .filter_map(|i| item_list.get(i))
+            .map(|item| {
+                DataTransferItem {
+                    kind: item.kind(),
+                    r#type: item.type_(),
+                }
+            })
+            .collect();
+        let data = types
+            .iter()
+            .filter(|format| *format != "Files")
+            .filter_map(|format| {
+                data_transfer
+                    .get_data(format)
+                    .ok()
+                    .filter(|data| !data.is_empty())
+                    .map(|data| (format.to_string(), data))
+            })
+            .collect();
+        let files = data_transfer
+            .files()
+            .map(|file_list| {
+                (0..file_list.length())
+                    .filter_map(|i| file_list.get(i))
+                    .collect()
+            })
+            .unwrap_or_default();
+        DataTransfer {
+            drop_effect: data_transfer.drop_effect(),
+            effect_allowed: data_transfer.effect_allowed(),
+            types,
+            items,
+            data,
+            files,
+        }
+    }
+
+    /// the plain text of the clipboard, the copied or cut text is only available
+    /// when it is set by a listener of the event.
+    /// The `clipboardData` is read directly since `web_sys::ClipboardEvent` is still unstable.
+    pub fn clipboard_event_mapper(event: crate::Event) -> ClipboardEvent {
+        let data = js_sys::Reflect::get(&event.0, &"clipboardData".into())
+            .ok()
+            .and_then(|data| data.dyn_into::<web_sys::DataTransfer>().ok())
+            .and_then(|data_transfer| data_transfer.get_data("text/plain").ok())
+            .filter(|data| !data.is_empty());
+        ClipboardEvent {
+            r#type: event.0.type_(),
+            data,
+        }
+    }
+
+    /// the id of the element the focus came from or went to
+    pub fn focus_event_mapper(event: crate::Event) -> FocusEvent {
+        let focus_event: &web_sys::FocusEvent =
+            event.0.dyn_ref().expect("Unable to cast as focus event");
+        let related_target_id = focus_event
+            .related_target()
+            .and_then(|target| target.dyn_into::<Element>().ok())
+            .map(|element| element.id())
+            .filter(|id| !id.is_empty());
+        FocusEvent {
+            r#type: focus_event.type_(),
+            related_target_id,
+        }
+    }
+
+    pub fn composition_event_mapper(event: crate::Event) -> CompositionEvent {
+        let composition_event: &web_sys::CompositionEvent = event
+            .0
+            .dyn_ref()
+            .expect("Unable to cast as composition event");
+        CompositionEvent {
+            r#type: composition_event.type_(),
+            data: composition_event.data().unwrap_or_default(),
+        }
+    }
+
+    /// the playback state of the audio or video element
+    pub fn media_event_mapper(event: crate::Event) -> MediaEvent {
+        let target = event.0.target().expect("Unable to get event target");
+        let media: &HtmlMediaElement =
+            target.dyn_ref().expect("Unable to cast as media element");
+        MediaEvent {
+            r#type: event.0.type_(),
+            current_time: media.current_time(),
+            duration: media.duration(),
+            paused: media.paused(),
+            ended: media.ended(),
+            volume: media.volume(),
+            muted: media.muted(),
         }
     }
 
+    pub fn animation_event_mapper(event: crate::Event) -> AnimationEvent {
+        let animation_event: &web_sys::AnimationEvent = event
+            .0
+            .dyn_ref()
+            .expect("Unable to cast as animation event");
+        AnimationEvent {
+            r#type: animation_event.type_(),
+            animation_name: animation_event.animation_name(),
+            elapsed_time: animation_event.elapsed_time(),
+            pseudo_element: animation_event.pseudo_element(),
+        }
+    }
+
+    pub fn transition_event_mapper(event: crate::Event) -> TransitionEvent {
+        let transition_event: &web_sys::TransitionEvent = event
+            .0
+            .dyn_ref()
+            .expect("Unable to cast as transition event");
+        TransitionEvent {
+            r#type: transition_event.type_(),
+            property_name: transition_event.property_name(),
+            elapsed_time: transition_event.elapsed_time(),
+            pseudo_element: transition_event.pseudo_element(),
+        }
+    }
+
+    /// the `detail` of a `CustomEvent`, `null` for the other events
+    pub fn custom_event_detail_mapper(event: crate::Event) -> JsValue {
+        event
+            .0
+            .dyn_ref::<web_sys::CustomEvent>()
+            .map(|custom_event| custom_event.detail())
+            .unwrap_or(JsValue::NULL)
+    }
+
     pub fn keyboard_event_mapper(event: crate::Event) -> KeyEvent {
         let key_event: &web_sys::KeyboardEvent =
             event.0.dyn_ref().expect("Unable to cast as key event");
@@ -88,36 +352,157 @@ pub mod mapper {
         };
         KeyEvent {
             key: key_event.key(),
+            code: key_event.code(),
             modifier,
             repeat: key_event.repeat(),
             location: key_event.location(),
+            is_composing: key_event.is_composing(),
+        }
+    }
+
+    pub fn wheel_event_mapper(event: crate::Event) -> WheelEvent {
+        let wheel: web_sys::WheelEvent = event
+            .0
+            .dyn_ref::<web_sys::WheelEvent>()
+            .expect("Unable to cast to wheel event")
+            .clone();
+        WheelEvent {
+            mouse: mouse_event_mapper(event),
+            delta_x: wheel.delta_x(),
+            delta_y: wheel.delta_y(),
+            delta_z: wheel.delta_z(),
+            delta_mode: DeltaMode::from(wheel.delta_mode()),
+        }
+    }
+
+    /// the scroll position and sizes of the scrolled element,
+    /// the scrolling element of the document is used when the document itself is scrolled
+    pub fn scroll_event_mapper(event: crate::Event) -> ScrollEvent {
+        let target = event.0.target().expect("can't get target");
+        let element: Element = match target.dyn_into::<Element>() {
+            Ok(element) => element,
+            Err(_) => {
+                crate::document()
+                    .scrolling_element()
+                    .expect("Unable to get the scrolling element")
+            }
+        };
+        ScrollEvent {
+            scroll_top: element.scroll_top(),
+            scroll_left: element.scroll_left(),
+            scroll_width: element.scroll_width(),
+            scroll_height: element.scroll_height(),
+            client_width: element.client_width(),
+            client_height: element.client_height(),
         }
     }
 
+    /// the value of the input, textarea or select element,
+    /// or the text of a contenteditable element.
+    /// The value is empty when the event is not from one of these elements.
     pub fn input_event_mapper(event: crate::Event) -> InputEvent {
-        let target: EventTarget =
-            event.0.target().expect("Unable to get event target");
-        let input: Option<&HtmlInputElement> = target.dyn_ref();
-        let textarea: Option<&HtmlTextAreaElement> = target.dyn_ref();
-        let input_event = if input.is_some() {
-            input.map(|input| {
-                InputEvent {
-                    value: input.value(),
-                }
-            })
-        } else if textarea.is_some() {
-            textarea.map(|textarea| {
-                InputEvent {
-                    value: textarea.value(),
+        let value = match event.0.target() {
+            Some(target) => {
+                if let Some(input) = target.dyn_ref::<HtmlInputElement>() {
+                    input.value()
+                } else if let Some(textarea) =
+                    target.dyn_ref::<HtmlTextAreaElement>()
+                {
+                    textarea.value()
+                } else if let Some(select) =
+                    target.dyn_ref::<HtmlSelectElement>()
+                {
+                    select.value()
+                } else if let Some(element) = target.dyn_ref::<HtmlElement>() {
+                    element.inner_text()
+                } else {
+                    String::new()
                 }
-            })
-        } else {
-            None
+            }
+            None => String::new(),
+        };
+        InputEvent { value }
+    }
+
+    /// the checked state of a checkbox or radio button,
+    /// this is `false` when the event is not from an input element
+    pub fn checked_mapper(event: crate::Event) -> bool {
+        event
+            .0
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+            .map(|input| input.checked())
+            .unwrap_or(false)
+    }
+
+    /// the values of the selected options of a select element,
+    /// a single select element has at most one selected value.
+    /// This is empty when the event is not from a select element.
+    pub fn selected_values_mapper(event: crate::Event) -> Vec<String> {
+        let select = event
+            .0
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlSelectElement>().ok());
+        match select {
+            Some(select) => {
+                let options = select.selected_options();
+                (0..options.length())
+                    .filter_map(|i| options.item(i))
+                    .map(|option| {
+                        option.unchecked_into::<HtmlOptionElement>().value()
+                    })
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// the value of a number or range input,
+    /// this is `None` when the input is empty or is not a valid number
+    /// or when the event is not from an input element
+    pub fn number_mapper(event: crate::Event) -> Option<f64> {
+        event
+            .0
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlInputElement>().ok())
+            .map(|input| input.value_as_number())
+            .filter(|value| !value.is_nan())
+    }
+
+    /// the fields of the submitted form, the file fields are not included.
+    /// This is empty when the event is not from a form element.
+    pub fn form_values_mapper(event: crate::Event) -> FormValues {
+        let form_data = event
+            .0
+            .target()
+            .and_then(|target| target.dyn_into::<HtmlFormElement>().ok())
+            .and_then(|form| web_sys::FormData::new_with_form(&form).ok());
+        let form_data = match form_data {
+            Some(form_data) => form_data,
+            None => return FormValues::default(),
         };
+        let mut form_values = FormValues::default();
+        let entries = js_sys::try_iter(&form_data)
+            .ok()
+            .and_then(|entries| entries);
+        if let Some(entries) = entries {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                let entry: js_sys::Array = entry.unchecked_into();
+                if let (Some(name), Some(value)) =
+                    (entry.get(0).as_string(), entry.get(1).as_string())
+                {
+                    form_values.insert(name, value);
+                }
+            }
+        }
+        form_values
+    }
 
-        input_event.expect(
-            "Expecting an input event from input element or textarea element",
-        )
+    /// the element where the event is dispatched to
+    pub fn target_element_mapper(event: crate::Event) -> web_sys::Element {
+        let target: EventTarget =
+            event.0.target().expect("Unable to get event target");
+        target.unchecked_into()
     }
 
 }
@@ -158,21 +543,8 @@ macro_rules! declare_events {
     }
 }
 
-#[inline]
-pub fn onscroll<CB, MSG>(cb: CB) -> crate::Attribute<MSG>
-where
-    CB: Fn((i32, i32)) -> MSG + 'static,
-    MSG: 'static,
-{
-    let webevent_to_scroll_offset = |event: crate::Event| {
-        let target = event.0.target().expect("can't get target");
-        let element: &web_sys::Element =
-            target.dyn_ref().expect("Cant cast to Element");
-        let scroll_top = element.scroll_top();
-        let scroll_left = element.scroll_left();
-        (scroll_top, scroll_left)
-    };
-    on_with_extractor("scroll", webevent_to_scroll_offset, cb)
+declare_events! {
+    onscroll : scroll => |ScrollEvent| scroll_event_mapper;
 }
 
 pub fn onresize<CB, MSG>(cb: CB) -> crate::Attribute<MSG>
@@ -205,13 +577,189 @@ declare_events! {
     onmouseover : mouseover =>|MouseEvent | mouse_event_mapper;
     onmouseout : mouseout =>|MouseEvent | mouse_event_mapper;
     onmouseup : mouseup =>|MouseEvent | mouse_event_mapper;
-    onpointerlockchange : pointerlockchange =>|MouseEvent | mouse_event_mapper;
-    onpointerlockerror : pointerlockerror =>|MouseEvent | mouse_event_mapper;
-    onselect : select =>|MouseEvent | mouse_event_mapper;
-    onwheel : wheel =>|MouseEvent | mouse_event_mapper;
     ondoubleclick : doubleclick =>|MouseEvent | mouse_event_mapper;
 }
 
+declare_events! {
+    onwheel : wheel => |WheelEvent| wheel_event_mapper;
+}
+
+// touch events
+declare_events! {
+    ontouchstart : touchstart => |TouchEvent| touch_event_mapper;
+    ontouchend : touchend => |TouchEvent| touch_event_mapper;
+    ontouchmove : touchmove => |TouchEvent| touch_event_mapper;
+    ontouchcancel : touchcancel => |TouchEvent| touch_event_mapper;
+}
+
+// pointer events
+declare_events! {
+    onpointerdown : pointerdown => |PointerEvent| pointer_event_mapper;
+    onpointerup : pointerup => |PointerEvent| pointer_event_mapper;
+    onpointermove : pointermove => |PointerEvent| pointer_event_mapper;
+    onpointerover : pointerover => |PointerEvent| pointer_event_mapper;
+    onpointerout : pointerout => |PointerEvent| pointer_event_mapper;
+    onpointerenter : pointerenter => |PointerEvent| pointer_event_mapper;
+    onpointerleave : pointerleave => |PointerEvent| pointer_event_mapper;
+    onpointercancel : pointercancel => |PointerEvent| pointer_event_mapper;
+    ongotpointercapture : gotpointercapture => |PointerEvent| pointer_event_mapper;
+    onlostpointercapture : lostpointercapture => |PointerEvent| pointer_event_mapper;
+}
+
+// drag and drop events
+declare_events! {
+    ondrag : drag => |DragEvent| drag_event_mapper;
+    ondragstart : dragstart => |DragEvent| drag_event_mapper;
+    ondragend : dragend => |DragEvent| drag_event_mapper;
+    ondragenter : dragenter => |DragEvent| drag_event_mapper;
+    ondragleave : dragleave => |DragEvent| drag_event_mapper;
+    ondragover : dragover => |DragEvent| drag_event_mapper;
+    ondrop : drop => |DragEvent| drag_event_mapper;
+}
+
+/// The data and the allowed effects which are set when the drag starts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DragData {
+    data: Vec<(String, String)>,
+    effect_allowed: Option<String>,
+}
+
+impl DragData {
+    /// drag this string payload with this format, ie: `text/plain`
+    pub fn new(format: &str, data: &str) -> Self {
+        DragData::default().with_data(format, data)
+    }
+
+    /// also drag this string payload with this format
+    pub fn with_data(mut self, format: &str, data: &str) -> Self {
+        self.data.push((format.to_string(), data.to_string()));
+        self
+    }
+
+    /// the effects which are allowed for the drop target,
+    /// ie: `move`, `copy`, `copyMove`, `all`
+    pub fn effect_allowed(mut self, effect_allowed: &str) -> Self {
+        self.effect_allowed = Some(effect_allowed.to_string());
+        self
+    }
+
+    fn set_to(&self, event: &crate::Event) {
+        let data_transfer = event
+            .dyn_ref::<web_sys::DragEvent>()
+            .and_then(|drag| drag.data_transfer());
+        if let Some(data_transfer) = data_transfer {
+            for (format, data) in self.data.iter() {
+                if let Err(error) = data_transfer.set_data(format, data) {
+                    crate::log!("Unable to set the drag data: {:?}", error);
+                }
+            }
+            if let Some(ref effect_allowed) = self.effect_allowed {
+                data_transfer.set_effect_allowed(effect_allowed);
+            }
+        }
+    }
+}
+
+/// Same as `ondragstart`, this also sets the drag data and the allowed effects when
+/// the drag starts. The element also needs the `draggable` attribute.
+/// ```ignore
+///     div(
+///         vec![
+///             draggable(true),
+///             ondragstart_with(DragData::new("text/plain", &card.id).effect_allowed("move"),
+///                 |_| Msg::DragCard(card.id)),
+///         ],
+///         vec![text(&card.title)],
+///     )
+/// ```
+pub fn ondragstart_with<CB, MSG>(
+    drag_data: DragData,
+    cb: CB,
+) -> crate::Attribute<MSG>
+where
+    CB: Fn(DragEvent) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_with_extractor(
+        "dragstart",
+        move |event: crate::Event| {
+            drag_data.set_to(&event);
+            drag_event_mapper(event)
+        },
+        cb,
+    )
+}
+
+/// Listen to the `CustomEvent` with this name, ie: the events emitted by a JS library,
+/// the `detail` of the event is passed to the callback.
+/// ```ignore
+///     on_custom("chart-select", |detail: JsValue| {
+///         Msg::SelectPoint(detail.as_f64().unwrap_or_default())
+///     })
+/// ```
+pub fn on_custom<CB, MSG>(
+    event_name: &'static str,
+    cb: CB,
+) -> crate::Attribute<MSG>
+where
+    CB: Fn(JsValue) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_with_extractor(event_name, custom_event_detail_mapper, cb)
+}
+
+/// Listen to the `CustomEvent` with this name and decode its `detail` into `T`,
+/// the detail is converted to JSON then deserialized with serde.
+/// ```ignore
+///     #[derive(Deserialize)]
+///     struct Selection { start: u32, end: u32 }
+///
+///     on_custom_with_serde("editor-select", |selection: Result<Selection, _>| {
+///         match selection {
+///             Ok(selection) => Msg::Select(selection.start, selection.end),
+///             Err(error) => Msg::InvalidSelection(error.to_string()),
+///         }
+///     })
+/// ```
+#[cfg(feature = "with-serde")]
+pub fn on_custom_with_serde<T, CB, MSG>(
+    event_name: &'static str,
+    cb: CB,
+) -> crate::Attribute<MSG>
+where
+    T: serde::de::DeserializeOwned + 'static,
+    CB: Fn(Result<T, serde_json::Error>) -> MSG + 'static,
+    MSG: 'static,
+{
+    on_with_extractor(
+        event_name,
+        |event: crate::Event| {
+            let detail = custom_event_detail_mapper(event);
+            let json = js_sys::JSON::stringify(&detail)
+                .ok()
+                .and_then(|json| json.as_string())
+                .unwrap_or_else(|| "null".to_string());
+            serde_json::from_str(&json)
+        },
+        cb,
+    )
+}
+
+// pointer lock and selection events, these are not mouse events
+// and carry no data
+declare_events! {
+    onpointerlockchange : pointerlockchange;
+    onpointerlockerror : pointerlockerror;
+    onselect : select;
+}
+
+// lifecycle events, these are dispatched by the DomUpdater
+// right after the element is inserted and right before it is removed from the DOM
+declare_events! {
+    on_mount : mount => |web_sys::Element| target_element_mapper;
+    on_unmount : unmount => |web_sys::Element| target_element_mapper;
+}
+
 // keyboard events
 declare_events! {
     onkeydown : keydown =>|KeyEvent| keyboard_event_mapper;
@@ -219,21 +767,87 @@ declare_events! {
     onkeyup : keyup =>|KeyEvent| keyboard_event_mapper;
 }
 
-// focus events
+// focus events, the related target is the element the focus came from or went to
+declare_events! {
+    onfocus : focus => |FocusEvent| focus_event_mapper;
+    onblur : blur => |FocusEvent| focus_event_mapper;
+    /// like `onfocus` but bubbles from the descendants of the element
+    onfocusin : focusin => |FocusEvent| focus_event_mapper;
+    /// like `onblur` but bubbles from the descendants of the element
+    onfocusout : focusout => |FocusEvent| focus_event_mapper;
+}
+
+// clipboard events
+declare_events! {
+    oncopy : copy => |ClipboardEvent| clipboard_event_mapper;
+    oncut : cut => |ClipboardEvent| clipboard_event_mapper;
+    /// the pasted text is in the `data` of the event
+    onpaste : paste => |ClipboardEvent| clipboard_event_mapper;
+}
+
+// composition events of an input method editor, ie: for entering CJK text
 declare_events! {
-    onfocus : focus;
-    onblur : blur;
+    oncompositionstart : compositionstart => |CompositionEvent| composition_event_mapper;
+    oncompositionupdate : compositionupdate => |CompositionEvent| composition_event_mapper;
+    oncompositionend : compositionend => |CompositionEvent| composition_event_mapper;
+}
+
+// media events of the audio and video elements
+declare_events! {
+    onplay : play => |MediaEvent| media_event_mapper;
+    onplaying : playing => |MediaEvent| media_event_mapper;
+    onpause : pause => |MediaEvent| media_event_mapper;
+    /// fired as the playback position changes while playing and when seeking
+    ontimeupdate : timeupdate => |MediaEvent| media_event_mapper;
+    onended : ended => |MediaEvent| media_event_mapper;
+    /// the duration of the media is known from here on
+    onloadedmetadata : loadedmetadata => |MediaEvent| media_event_mapper;
+    ondurationchange : durationchange => |MediaEvent| media_event_mapper;
+    oncanplay : canplay => |MediaEvent| media_event_mapper;
+    onwaiting : waiting => |MediaEvent| media_event_mapper;
+    onseeking : seeking => |MediaEvent| media_event_mapper;
+    onseeked : seeked => |MediaEvent| media_event_mapper;
+    onvolumechange : volumechange => |MediaEvent| media_event_mapper;
+    onratechange : ratechange => |MediaEvent| media_event_mapper;
+}
+
+// CSS animation and transition events
+declare_events! {
+    onanimationstart : animationstart => |AnimationEvent| animation_event_mapper;
+    onanimationiteration : animationiteration => |AnimationEvent| animation_event_mapper;
+    onanimationend : animationend => |AnimationEvent| animation_event_mapper;
+    ontransitionstart : transitionstart => |TransitionEvent| transition_event_mapper;
+    ontransitionend : transitionend => |TransitionEvent| transition_event_mapper;
 }
 
 // form events
 declare_events! {
     onreset : reset;
-    onsubmit : submit;
+}
+
+declare_events! {
+    /// the fields of the form are passed when the form is submitted,
+    /// the page is not reloaded
+    onsubmit : submit => |FormValues| form_values_mapper;
 }
 
 declare_events! {
     oninput : input => |InputEvent| input_event_mapper;
     onchange : change => | InputEvent | input_event_mapper;
+    /// the checked state of a checkbox or radio button when it changed.
+    ///
+    /// Note: this listens to the same `change` event as `onchange`, and the listeners are
+    /// diffed by their event name only. Switching an element from `onchange` to
+    /// `onchange_checked` or `onchange_selected` keeps the listener of the first render,
+    /// give the element a different `key` so it is replaced instead.
+    onchange_checked : change => |bool| checked_mapper;
+    /// the selected values of a select element, including `<select multiple>`, when they changed.
+    ///
+    /// Note: this also listens to the `change` event, see the note on `onchange_checked`
+    /// about switching between the `change` listeners on the same element.
+    onchange_selected : change => |Vec<String>| selected_values_mapper;
+    /// the value of a number or range input as it is typed
+    oninput_number : input => |Option<f64>| number_mapper;
 }
 declare_events! {
     onbroadcast : broadcast;
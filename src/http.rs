@@ -27,16 +27,14 @@ impl Http {
         APP: Component<MSG> + 'static,
         MSG: 'static,
     {
-        let response_text_decoder = Callback::from(response_text_decoder);
-        let cb = Callback::from(cb);
         let cb_clone = cb.clone();
         let response_decoder = move |js_value: JsValue| {
             let response_text =
                 js_value.as_string().expect("There's no string value");
-            let msg_value = response_text_decoder.emit(response_text);
-            cb.emit(Ok(msg_value))
+            let msg_value = response_text_decoder(response_text);
+            cb(Ok(msg_value))
         };
-        let fail_cb = move |js_value| cb_clone.emit(Err(js_value));
+        let fail_cb = move |js_value| cb_clone(Err(js_value));
         Self::fetch_with_response_decoder(url, response_decoder, fail_cb)
     }
 
@@ -63,8 +61,8 @@ impl Http {
             let response_decoder = response_decoder.clone();
 
             let decoder_and_dispatcher = move |js_value: JsValue| {
-                let msg = response_decoder.emit(js_value);
-                program_clone.dispatch(msg);
+                let msgs = response_decoder.emit(js_value);
+                program_clone.dispatch_multiple(msgs);
             };
 
             let fail_cb = fail_cb.clone();
@@ -93,7 +91,7 @@ impl Http {
                         }
                     } else {
                         program_clone_status_err
-                            .dispatch(fail_cb.emit(js_value));
+                            .dispatch_multiple(fail_cb.emit(js_value));
                     }
                 });
 
@@ -102,7 +100,7 @@ impl Http {
                 Closure::once(move |js_value: JsValue| {
                     let fail_cb = fail_cb2.clone();
                     program_clone_response_error
-                        .dispatch(fail_cb.emit(js_value));
+                        .dispatch_multiple(fail_cb.emit(js_value));
                 });
 
             promise.then(&cb).catch(&fail_closure);
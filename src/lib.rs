@@ -150,6 +150,7 @@ pub mod dom;
 #[macro_use]
 pub mod html;
 mod component;
+pub mod custom_element;
 pub mod html_array;
 pub mod html_extra;
 mod program;
@@ -165,13 +166,30 @@ pub mod svg_extra;
 pub mod test_fixtures;
 mod util;
 
-pub use component::Component;
-pub use dom::DomUpdater;
-pub use program::Program;
+pub use component::{
+    Component,
+    StatelessComponent,
+    ViewError,
+};
+pub use custom_element::CustomElement;
+pub use dom::{
+    DomUpdater,
+    NodeRef,
+    PatchError,
+};
+pub use program::{
+    Debugger,
+    Measurements,
+    Middleware,
+    Program,
+    Schedule,
+    Scheduling,
+};
 pub use sauron_vdom::{
     diff,
     Callback,
     Dispatch,
+    Rate,
     Text,
 };
 pub use util::{
@@ -217,6 +235,7 @@ impl Deref for Event{
 /// App code.
 pub type Node<MSG> = sauron_vdom::Node<&'static str, Event, MSG>;
 pub type Element<MSG> = sauron_vdom::Element<&'static str, Event, MSG>;
+pub type Lazy<MSG> = sauron_vdom::Lazy<&'static str, Event, MSG>;
 pub type Patch<'a, MSG> = sauron_vdom::Patch<'a, &'static str, Event, MSG>;
 pub type Attribute<MSG> = sauron_vdom::Attribute<Event, MSG>;
 pub type Cmd<APP, MSG> = sauron_vdom::Cmd<Program<APP, MSG>, MSG>;
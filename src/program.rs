@@ -1,17 +1,41 @@
 use crate::{
+    component::{
+        ChildComponents,
+        MountedChild,
+    },
     Cmd,
     Component,
     Dispatch,
     DomUpdater,
+    PatchError,
 };
 use std::{
-    cell::RefCell,
+    cell::{
+        Cell,
+        RefCell,
+    },
+    collections::VecDeque,
     rc::Rc,
 };
-#[cfg(not(feature = "no_request_animation_frame"))]
-use wasm_bindgen::closure::Closure;
 use web_sys::Node;
 
+use debugger::Recorder;
+pub use debugger::Debugger;
+pub use measurements::Measurements;
+pub use middleware::Middleware;
+pub use scheduling::{
+    Schedule,
+    Scheduling,
+};
+
+mod debugger;
+mod measurements;
+mod middleware;
+mod scheduling;
+
+type MeasurementsSink = Box<dyn Fn(&Measurements)>;
+type ErrorHandler = Box<dyn Fn(&PatchError)>;
+
 /// Holds the app and the dom updater
 /// This is passed into the event listener and the dispatch program
 /// will be called after the event is triggered.
@@ -21,6 +45,29 @@ where
 {
     pub app: Rc<RefCell<APP>>,
     pub dom_updater: Rc<RefCell<DomUpdater<Self, MSG>>>,
+    /// the stateful child components rendered in the view of the app
+    child_components: ChildComponents,
+    /// the teardown of the subscriptions made by the Cmds, such as event listeners
+    /// attached to the window. This is executed when the program is unmounted.
+    teardowns: RefCell<Vec<Box<dyn FnOnce()>>>,
+    /// an unmounted program ignores the dispatched msgs
+    unmounted: Cell<bool>,
+    /// the msgs which are dispatched but are not yet executed
+    pending_msgs: RefCell<VecDeque<MSG>>,
+    /// determines when the dispatched msgs are executed
+    scheduling: Scheduling,
+    /// the execution of the pending msgs is already scheduled
+    execution_scheduled: Cell<bool>,
+    /// the pending msgs are currently being executed
+    executing: Cell<bool>,
+    /// receives the measurements of each execution
+    measurements_sink: RefCell<Option<MeasurementsSink>>,
+    /// records the msgs and app states when the debugger is enabled
+    recorder: RefCell<Option<Rc<dyn Recorder<APP, MSG>>>>,
+    /// the middlewares which are called around the update of the app
+    middlewares: RefCell<Vec<Rc<dyn Middleware<APP, MSG>>>>,
+    /// called when the patching of the DOM failed
+    error_handler: RefCell<Option<ErrorHandler>>,
 }
 
 impl<APP, MSG> Program<APP, MSG>
@@ -30,12 +77,25 @@ where
 {
     /// Create an Rc wrapped instance of program, initializing DomUpdater with the initial view
     /// and root node, but doesn't mount it yet.
-    fn new(app: APP, root_node: &Node) -> Rc<Self> {
+    fn new(app: APP, root_node: &Node, scheduling: Scheduling) -> Rc<Self> {
+        let child_components = ChildComponents::new();
+        let view = child_components.render(|| app.view());
         let dom_updater: DomUpdater<Self, MSG> =
-            DomUpdater::new(app.view(), root_node);
+            DomUpdater::new(view, root_node);
         let program = Program {
             app: Rc::new(RefCell::new(app)),
             dom_updater: Rc::new(RefCell::new(dom_updater)),
+            child_components,
+            teardowns: RefCell::new(vec![]),
+            unmounted: Cell::new(false),
+            pending_msgs: RefCell::new(VecDeque::new()),
+            scheduling,
+            execution_scheduled: Cell::new(false),
+            executing: Cell::new(false),
+            measurements_sink: RefCell::new(None),
+            recorder: RefCell::new(None),
+            middlewares: RefCell::new(vec![]),
+            error_handler: RefCell::new(None),
         };
         let rc_program: Rc<Self> = Rc::new(program);
         // call the init of the component
@@ -47,15 +107,19 @@ where
     /// Creates an Rc wrapped instance of Program and mount the app view to the
     /// given root_node
     pub fn new_replace_mount(app: APP, root_node: &Node) -> Rc<Self> {
-        let program = Self::new(app, root_node);
-        program.start_replace_mount();
-        program
+        Self::new_replace_mount_with_scheduling(
+            app,
+            root_node,
+            Scheduling::default(),
+        )
     }
 
     pub fn new_append_to_mount(app: APP, root_node: &Node) -> Rc<Self> {
-        let program = Self::new(app, root_node);
-        program.start_append_to_mount();
-        program
+        Self::new_append_to_mount_with_scheduling(
+            app,
+            root_node,
+            Scheduling::default(),
+        )
     }
 
     /// Instantiate the app and then append it to the document body
@@ -63,6 +127,112 @@ where
         Self::new_append_to_mount(app, &crate::body())
     }
 
+    /// Same as `new_replace_mount`, with the scheduling of the rendering
+    pub fn new_replace_mount_with_scheduling(
+        app: APP,
+        root_node: &Node,
+        scheduling: Scheduling,
+    ) -> Rc<Self> {
+        let program = Self::new(app, root_node, scheduling);
+        program.start_replace_mount();
+        program
+    }
+
+    /// Same as `new_append_to_mount`, with the scheduling of the rendering
+    pub fn new_append_to_mount_with_scheduling(
+        app: APP,
+        root_node: &Node,
+        scheduling: Scheduling,
+    ) -> Rc<Self> {
+        let program = Self::new(app, root_node, scheduling);
+        program.start_append_to_mount();
+        program
+    }
+
+    /// Same as `mount_to_body`, with the scheduling of the rendering
+    pub fn mount_to_body_with_scheduling(
+        app: APP,
+        scheduling: Scheduling,
+    ) -> Rc<Self> {
+        Self::new_append_to_mount_with_scheduling(
+            app,
+            &crate::body(),
+            scheduling,
+        )
+    }
+
+    /// Remove the view of the app from the DOM, and drop all the closures of the
+    /// event listeners, the child components and the subscriptions made by the Cmds.
+    /// Any pending and further msgs dispatched to this program are ignored.
+    pub fn unmount(&self) {
+        if self.unmounted.replace(true) {
+            return;
+        }
+        self.dom_updater.borrow_mut().unmount();
+        self.child_components.unmount_all();
+        let teardowns: Vec<Box<dyn FnOnce()>> =
+            self.teardowns.borrow_mut().drain(..).collect();
+        for teardown in teardowns {
+            teardown();
+        }
+    }
+
+    /// Register the sink which receives the measurements of each execution
+    /// of the dispatched msgs, from the update of the app up to the patching of the DOM.
+    /// ```ignore
+    ///     program.set_measurements_sink(|measurements| {
+    ///         sauron::log!("{}", measurements);
+    ///     });
+    /// ```
+    pub fn set_measurements_sink<F>(&self, sink: F)
+    where
+        F: Fn(&Measurements) + 'static,
+    {
+        *self.measurements_sink.borrow_mut() = Some(Box::new(sink));
+    }
+
+    /// Register the handler which is called when the patching of the DOM failed,
+    /// ie: when the DOM was modified outside of the program.
+    /// The DOM is already rendered anew from the view when the handler is called.
+    /// Without a handler, the error is logged to the console.
+    /// ```ignore
+    ///     program.set_error_handler(|error| {
+    ///         report_to_server(error.to_string());
+    ///     });
+    /// ```
+    pub fn set_error_handler<F>(&self, handler: F)
+    where
+        F: Fn(&PatchError) + 'static,
+    {
+        *self.error_handler.borrow_mut() = Some(Box::new(handler));
+    }
+
+    /// Add a middleware which is called around each update of the app
+    pub fn add_middleware<M>(&self, middleware: M)
+    where
+        M: Middleware<APP, MSG> + 'static,
+    {
+        self.middlewares.borrow_mut().push(Rc::new(middleware));
+    }
+
+    /// checks whether the program is still mounted
+    pub fn is_mounted(&self) -> bool {
+        !self.unmounted.get()
+    }
+
+    /// Add a teardown which is executed when the program is unmounted.
+    /// This is used by Cmds which subscribes to events outside of the view.
+    pub(crate) fn add_teardown<F>(&self, teardown: F)
+    where
+        F: FnOnce() + 'static,
+    {
+        if self.unmounted.get() {
+            teardown();
+        } else {
+            self.teardowns.borrow_mut().push(Box::new(teardown));
+        }
+    }
+
     fn start_append_to_mount(self: &Rc<Self>) {
         self.dom_updater.borrow_mut().append_to_mount(self)
     }
@@ -71,40 +241,183 @@ where
         self.dom_updater.borrow_mut().replace_mount(self)
     }
 
+    /// Schedule the execution of the pending msgs,
+    /// the execution is scheduled only once no matter how many msgs are dispatched.
+    fn schedule_execution(self: &Rc<Self>) {
+        if self.execution_scheduled.replace(true) {
+            return;
+        }
+        let program_clone = Rc::clone(self);
+        self.scheduling.schedule(move || {
+            program_clone.execution_scheduled.set(false);
+            program_clone.execute_pending();
+        });
+    }
+
     /// This is called when an event is triggered in the html DOM.
     /// The sequence of things happening here:
-    /// - The app component update is executed.
-    /// - The returned Cmd from the component update is then emitted.
-    /// - The view is reconstructed with the new state of the app.
+    /// - The app component update is executed for each of the pending msgs.
+    /// - The returned Cmd from each of the component update is then emitted in order.
+    /// - The view is reconstructed once with the new state of the app.
     /// - The dom is updated with the newly reconstructed view.
-    fn dispatch_inner(self: &Rc<Self>, msg: MSG) {
-        #[cfg(feature = "measure")]
-        let t1 = crate::now();
-        // update the app and emit the cmd returned from the update
+    ///
+    /// The msgs dispatched while the dom is updated, such as the ones from `on_mount`
+    /// are then executed in another round.
+    fn execute_pending(self: &Rc<Self>) {
+        if self.executing.replace(true) {
+            return;
+        }
+        let _executing = ExecutingGuard(&self.executing);
+        loop {
+            // the msgs which are pending when the program is unmounted are dropped
+            if self.unmounted.get() {
+                self.pending_msgs.borrow_mut().clear();
+                break;
+            }
+            // nothing is measured when there is no sink for the measurements
+            let measuring = self.measurements_sink.borrow().is_some();
+            let mut measurements = Measurements::default();
+            let t1 = if measuring { crate::now() } else { 0.0 };
+            // update the app and emit the cmd returned from each update,
+            // msgs dispatched by the cmds are also executed before the view is rendered
+            loop {
+                let msg = self.pending_msgs.borrow_mut().pop_front();
+                match msg {
+                    Some(msg) => {
+                        if self.update_app(msg) {
+                            measurements.msg_count += 1;
+                        }
+                    }
+                    None => break,
+                }
+            }
+            // no msgs are pending or they are all dropped by the middlewares
+            if measurements.msg_count == 0 {
+                break;
+            }
+            if !measuring {
+                self.render_measured(None);
+                continue;
+            }
+            measurements.update_took = crate::now() - t1;
+            self.render_measured(Some(&mut measurements));
+            measurements.total_took = crate::now() - t1;
+            if let Some(sink) = self.measurements_sink.borrow().as_ref() {
+                sink(&measurements);
+            }
+        }
+    }
+
+    /// Update the app with the msg passing through the middlewares,
+    /// then execute the returned Cmd.
+    /// Returns false if the msg is dropped by a middleware.
+    fn update_app(self: &Rc<Self>, msg: MSG) -> bool {
+        let middlewares = self.middlewares.borrow().clone();
+        let mut msg = Some(msg);
+        for middleware in middlewares.iter() {
+            msg = match msg {
+                Some(msg) => middleware.before_update(&self.app.borrow(), msg),
+                None => break,
+            };
+        }
+        let msg = match msg {
+            Some(msg) => msg,
+            None => return false,
+        };
+        let recorder = self.recorder.borrow().clone();
+        if let Some(ref recorder) = recorder {
+            recorder.before_update(&msg);
+        }
         let cmd = self.app.borrow_mut().update(msg);
+        if let Some(ref recorder) = recorder {
+            recorder.after_update(&self.app.borrow());
+        }
+        for middleware in middlewares.iter() {
+            middleware.after_update(&self.app.borrow(), &cmd);
+        }
         cmd.emit(self);
-        crate::log("Executing cmd..");
-        #[cfg(feature = "measure")]
-        let t2 = {
-            let t2 = crate::now();
-            crate::log!("app update took: {}ms", t2 - t1);
-            t2
+        true
+    }
+
+    /// Render the view with the current state of the app
+    pub(crate) fn render(self: &Rc<Self>) {
+        self.render_measured(None);
+    }
+
+    fn render_measured(
+        self: &Rc<Self>,
+        mut measurements: Option<&mut Measurements>,
+    ) {
+        let t1 = if measurements.is_some() {
+            crate::now()
+        } else {
+            0.0
         };
         // a new view is created due to the app update
-        let view = self.app.borrow().view();
-        #[cfg(feature = "measure")]
-        let t3 = {
-            let t3 = crate::now();
-            crate::log!("app view took: {}ms", t3 - t2);
-            t3
-        };
+        let view = self.child_components.render(|| self.app.borrow().view());
+        if let Some(measurements) = measurements.as_mut() {
+            measurements.view_took = crate::now() - t1;
+        }
+        let middlewares = self.middlewares.borrow().clone();
+        for middleware in middlewares.iter() {
+            middleware.before_patch(&self.app.borrow(), &view);
+        }
         // update the last DOM node tree with this new view
-        self.dom_updater.borrow_mut().update_dom(self, view);
-        #[cfg(feature = "measure")]
-        {
-            let t4 = crate::now();
-            crate::log!("dom update took: {}ms", t4 - t3);
-        };
+        let updated = self.dom_updater.borrow_mut().update_dom_measured(
+            self,
+            view,
+            measurements,
+        );
+        self.child_components.remove_unused();
+        if let Err(error) = updated {
+            match *self.error_handler.borrow() {
+                Some(ref handler) => handler(&error),
+                None => crate::log!("Error in patching the dom: {}", error),
+            }
+        }
+    }
+}
+
+/// Resets the executing flag when the execution ends,
+/// even when the update of the app panicked
+struct ExecutingGuard<'a>(&'a Cell<bool>);
+
+impl<'a> Drop for ExecutingGuard<'a> {
+    fn drop(&mut self) {
+        self.0.set(false);
+    }
+}
+
+impl<APP, MSG> Program<APP, MSG>
+where
+    MSG: Clone + 'static,
+    APP: Component<MSG> + Clone + 'static,
+{
+    /// Start recording the msgs dispatched to this program together with the state
+    /// of the app after each update. The returned debugger can step back and forth
+    /// through the recorded states.
+    /// ```ignore
+    ///     let program = Program::mount_to_body(App::new());
+    ///     let debugger = program.enable_debugger();
+    ///     // later on
+    ///     debugger.step_back();
+    /// ```
+    pub fn enable_debugger(self: &Rc<Self>) -> Debugger<APP, MSG> {
+        Debugger::new(self)
+    }
+}
+
+impl<APP, MSG> MountedChild for Program<APP, MSG>
+where
+    MSG: 'static,
+    APP: Component<MSG> + 'static,
+{
+    fn root_node(&self) -> Node {
+        self.dom_updater.borrow().root_node()
+    }
+
+    fn unmount(&self) {
+        Program::unmount(self)
     }
 }
 
@@ -115,19 +428,42 @@ where
     MSG: 'static,
     APP: Component<MSG> + 'static,
 {
-    #[cfg(not(feature = "no_request_animation_frame"))]
     fn dispatch(self: &Rc<Self>, msg: MSG) {
-        let program_clone = Rc::clone(self);
-        let closure_raf: Closure<dyn FnMut() + 'static> =
-            Closure::once(move || {
-                program_clone.dispatch_inner(msg);
-            });
-        crate::request_animation_frame(&closure_raf);
-        closure_raf.forget();
+        self.dispatch_multiple(vec![msg])
     }
 
-    #[cfg(feature = "no_request_animation_frame")]
-    fn dispatch(self: &Rc<Self>, msg: MSG) {
-        self.dispatch_inner(msg)
+    /// The msgs are all executed before the view is rendered,
+    /// no update, view nor diff happens when there are no msgs.
+    fn dispatch_multiple(self: &Rc<Self>, msgs: Vec<MSG>) {
+        if msgs.is_empty() || self.unmounted.get() {
+            return;
+        }
+        // the msgs are ignored while the debugger is showing a past state
+        let paused = self
+            .recorder
+            .borrow()
+            .as_ref()
+            .map(|recorder| recorder.is_paused())
+            .unwrap_or(false);
+        if paused {
+            return;
+        }
+        self.pending_msgs.borrow_mut().extend(msgs);
+        // msgs dispatched while executing are executed by the ongoing execution
+        if self.executing.get() {
+            return;
+        }
+        match self.scheduling {
+            Scheduling::Sync => {
+                // messages dispatched while the dom is being mounted, such as the ones
+                // from `on_mount` are executed in the next frame
+                if self.dom_updater.try_borrow_mut().is_err() {
+                    self.schedule_execution();
+                } else {
+                    self.execute_pending();
+                }
+            }
+            _ => self.schedule_execution(),
+        }
     }
 }
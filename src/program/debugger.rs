@@ -0,0 +1,231 @@
+//! A time-travel debugger which records the msgs dispatched to a program
+//! together with a snapshot of the app state after each update.
+//!
+//! The recorded states can be stepped back and forth, the view is re-rendered with the
+//! state that is shown. Msgs dispatched from the view are ignored while a past state is shown.
+//! The state of the stateful child components is not recorded.
+use super::Program;
+use crate::Component;
+use std::{
+    cell::{
+        Cell,
+        RefCell,
+    },
+    rc::Rc,
+};
+
+/// Receives the msgs and the updated app state of the program
+pub(super) trait Recorder<APP, MSG> {
+    /// called with the msg right before the app is updated with it
+    fn before_update(&self, msg: &MSG);
+    /// called with the app right after it is updated
+    fn after_update(&self, app: &APP);
+    /// a past state is currently shown, the dispatched msgs are ignored
+    fn is_paused(&self) -> bool;
+}
+
+struct History<APP, MSG> {
+    /// the state of the app when the debugger is enabled
+    initial: APP,
+    /// the msg which the app is currently being updated with
+    updating_msg: RefCell<Option<MSG>>,
+    /// the recorded msgs and the state of the app after the update
+    entries: RefCell<Vec<(MSG, APP)>>,
+    /// the state which is currently shown, `None` if it is the latest state
+    position: Cell<Option<usize>>,
+}
+
+impl<APP, MSG> Recorder<APP, MSG> for History<APP, MSG>
+where
+    APP: Clone,
+    MSG: Clone,
+{
+    fn before_update(&self, msg: &MSG) {
+        *self.updating_msg.borrow_mut() = Some(msg.clone());
+    }
+
+    fn after_update(&self, app: &APP) {
+        if let Some(msg) = self.updating_msg.borrow_mut().take() {
+            self.entries.borrow_mut().push((msg, app.clone()));
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        self.position.get().is_some()
+    }
+}
+
+/// A handle to the debugger of a program, created with `Program::enable_debugger`.
+///
+/// The states are indexed from `0` which is the state of the app when the debugger is enabled,
+/// up to `len()` which is the state after the last recorded msg.
+pub struct Debugger<APP, MSG>
+where
+    MSG: 'static,
+{
+    program: Rc<Program<APP, MSG>>,
+    history: Rc<History<APP, MSG>>,
+}
+
+impl<APP, MSG> Debugger<APP, MSG>
+where
+    APP: Component<MSG> + Clone + 'static,
+    MSG: Clone + 'static,
+{
+    pub(super) fn new(program: &Rc<Program<APP, MSG>>) -> Self {
+        let history = Rc::new(History {
+            initial: program.app.borrow().clone(),
+            updating_msg: RefCell::new(None),
+            entries: RefCell::new(vec![]),
+            position: Cell::new(None),
+        });
+        let recorder: Rc<dyn Recorder<APP, MSG>> = history.clone();
+        *program.recorder.borrow_mut() = Some(recorder);
+        Debugger {
+            program: Rc::clone(program),
+            history,
+        }
+    }
+
+    /// the number of recorded msgs
+    pub fn len(&self) -> usize {
+        self.history.entries.borrow().len()
+    }
+
+    /// returns true if there are no recorded msgs
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// the index of the state that is currently shown
+    pub fn position(&self) -> usize {
+        self.history.position.get().unwrap_or_else(|| self.len())
+    }
+
+    /// returns true if a past state is shown
+    pub fn is_paused(&self) -> bool {
+        self.history.is_paused()
+    }
+
+    /// the recorded msgs, in the order they were dispatched
+    pub fn msgs(&self) -> Vec<MSG> {
+        self.history
+            .entries
+            .borrow()
+            .iter()
+            .map(|(msg, _app)| msg.clone())
+            .collect()
+    }
+
+    /// Show the state before the currently shown state
+    pub fn step_back(&self) {
+        let position = self.position();
+        if position > 0 {
+            self.go_to(position - 1);
+        }
+    }
+
+    /// Show the state after the currently shown state
+    pub fn step_forward(&self) {
+        self.go_to(self.position() + 1);
+    }
+
+    /// Show the latest state, the dispatched msgs are executed again
+    pub fn resume(&self) {
+        self.go_to(self.len());
+    }
+
+    /// Re-render the view with the state at this index.
+    /// The index is capped to the latest state.
+    /// Nothing happens when this is called while the program is executing the msgs,
+    /// ie: from the update or the view of the app.
+    pub fn go_to(&self, index: usize) {
+        if self.is_executing() {
+            return;
+        }
+        let len = self.len();
+        let index = index.min(len);
+        let app = if index == 0 {
+            self.history.initial.clone()
+        } else {
+            self.history.entries.borrow()[index - 1].1.clone()
+        };
+        self.history.position.set(if index == len {
+            None
+        } else {
+            Some(index)
+        });
+        *self.program.app.borrow_mut() = app;
+        self.program.render();
+    }
+
+    /// Export the recorded msgs, this can be imported later on with `import_msgs`
+    /// to reproduce the same states. With the `with-serde` feature, the msgs can also be
+    /// exported as json with `export_json`.
+    pub fn export_msgs(&self) -> Vec<MSG> {
+        self.msgs()
+    }
+
+    /// Replace the recorded msgs with these msgs.
+    /// The msgs are replayed from the state of the app when the debugger is enabled,
+    /// the Cmds returned from the updates are not executed.
+    /// Nothing happens when this is called while the program is executing the msgs.
+    pub fn import_msgs(&self, msgs: Vec<MSG>) {
+        if self.is_executing() {
+            return;
+        }
+        let mut app = self.history.initial.clone();
+        let entries = msgs
+            .into_iter()
+            .map(|msg| {
+                let _cmd = app.update(msg.clone());
+                (msg, app.clone())
+            })
+            .collect();
+        *self.history.entries.borrow_mut() = entries;
+        self.resume();
+    }
+
+    /// Export the recorded msgs as a json array
+    #[cfg(feature = "with-serde")]
+    pub fn export_json(&self) -> Result<String, serde_json::Error>
+    where
+        MSG: serde::Serialize,
+    {
+        serde_json::to_string(&self.msgs())
+    }
+
+    /// Replace the recorded msgs with the msgs of this json array,
+    /// the recorded msgs are kept when the json can not be deserialized.
+    #[cfg(feature = "with-serde")]
+    pub fn import_json(&self, json: &str) -> Result<(), serde_json::Error>
+    where
+        MSG: serde::de::DeserializeOwned,
+    {
+        let msgs: Vec<MSG> = serde_json::from_str(json)?;
+        self.import_msgs(msgs);
+        Ok(())
+    }
+
+    /// the app is borrowed while the program is executing the msgs,
+    /// the states can not be switched then
+    fn is_executing(&self) -> bool {
+        if self.program.executing.get() {
+            crate::log!(
+                "The debugger can not switch the state while the msgs are executed"
+            );
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl<APP, MSG> Clone for Debugger<APP, MSG> {
+    fn clone(&self) -> Self {
+        Debugger {
+            program: Rc::clone(&self.program),
+            history: Rc::clone(&self.history),
+        }
+    }
+}
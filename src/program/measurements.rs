@@ -0,0 +1,49 @@
+use std::fmt;
+
+/// The measurements of one execution of the dispatched msgs,
+/// from the update of the app up to the patching of the DOM.
+/// The durations are in milliseconds.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Measurements {
+    /// the number of msgs which are applied to the app in this execution
+    pub msg_count: usize,
+    /// the time it took to update the app with the msgs
+    pub update_took: f64,
+    /// the time it took to create the new view
+    pub view_took: f64,
+    /// the time it took to diff the new view with the current view
+    pub diff_took: f64,
+    /// the time it took to apply the patches to the DOM
+    pub patch_took: f64,
+    /// the total time it took for this execution
+    pub total_took: f64,
+    /// the number of patches applied to the DOM
+    pub patch_count: usize,
+    /// the number of nodes in the new view
+    pub node_count: usize,
+    /// the number of event listener closures created in the patching
+    pub closures_created: usize,
+    /// the number of event listener closures dropped in the patching
+    pub closures_dropped: usize,
+}
+
+impl fmt::Display for Measurements {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "msgs: {}, update: {:.2}ms, view: {:.2}ms, diff: {:.2}ms, \
+             patch: {:.2}ms, total: {:.2}ms, patches: {}, nodes: {}, \
+             closures created: {}, closures dropped: {}",
+            self.msg_count,
+            self.update_took,
+            self.view_took,
+            self.diff_took,
+            self.patch_took,
+            self.total_took,
+            self.patch_count,
+            self.node_count,
+            self.closures_created,
+            self.closures_dropped
+        )
+    }
+}
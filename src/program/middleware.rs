@@ -0,0 +1,29 @@
+use crate::{
+    Cmd,
+    Component,
+    Node,
+};
+
+/// Cross-cutting behavior around the update of the app, such as logging, analytics or
+/// authorization checks, added to the program with `Program::add_middleware`.
+///
+/// The middlewares are called in the order they are added.
+pub trait Middleware<APP, MSG>
+where
+    APP: Component<MSG> + 'static,
+    MSG: 'static,
+{
+    /// Called with the msg before the app is updated with it.
+    /// The returned msg is passed to the next middleware and then to the update,
+    /// returning `None` drops the msg.
+    fn before_update(&self, _app: &APP, msg: MSG) -> Option<MSG> {
+        Some(msg)
+    }
+
+    /// Called with the updated app and the Cmd returned from the update,
+    /// before the Cmd is executed.
+    fn after_update(&self, _app: &APP, _cmd: &Cmd<APP, MSG>) {}
+
+    /// Called with the new view of the app before it is patched into the DOM
+    fn before_patch(&self, _app: &APP, _view: &Node<MSG>) {}
+}
@@ -0,0 +1,97 @@
+use std::{
+    fmt,
+    rc::Rc,
+};
+use wasm_bindgen::{
+    closure::Closure,
+    JsCast,
+};
+
+/// A function which is called with the execution of the pending msgs,
+/// it decides when to call the execution.
+pub type Schedule = Rc<dyn Fn(Box<dyn FnOnce()>)>;
+
+/// Determines when the dispatched msgs are executed and the view is rendered.
+pub enum Scheduling {
+    /// The msgs are executed and rendered right away when they are dispatched.
+    /// This is useful in tests, where the dom needs to be updated right after the dispatch.
+    Sync,
+    /// The msgs are executed in the next animation frame,
+    /// all the msgs dispatched before the frame are rendered only once.
+    /// This is the default scheduling.
+    AnimationFrame,
+    /// The msgs are executed when the browser is idle,
+    /// This falls back to `AnimationFrame` when `requestIdleCallback` is not supported.
+    IdleCallback,
+    /// The msgs are executed when the supplied function calls the execution
+    Custom(Schedule),
+}
+
+impl Default for Scheduling {
+    fn default() -> Self {
+        Scheduling::AnimationFrame
+    }
+}
+
+impl Scheduling {
+    /// Create a custom scheduling from this function
+    pub fn custom<F>(schedule: F) -> Self
+    where
+        F: Fn(Box<dyn FnOnce()>) + 'static,
+    {
+        Scheduling::Custom(Rc::new(schedule))
+    }
+
+    /// Schedule the execution.
+    /// With `Sync` scheduling, this is only called for the msgs which are dispatched
+    /// while the DOM is being updated, ie: from `on_mount`, they are executed in the
+    /// next animation frame.
+    pub(super) fn schedule<F>(&self, execute: F)
+    where
+        F: FnOnce() + 'static,
+    {
+        match self {
+            Scheduling::Sync | Scheduling::AnimationFrame => {
+                request_animation_frame(execute)
+            }
+            Scheduling::IdleCallback => {
+                let closure: Closure<dyn FnMut()> = Closure::once(execute);
+                let window = crate::window();
+                let has_idle_callback = js_sys::Reflect::has(
+                    &window,
+                    &"requestIdleCallback".into(),
+                )
+                .unwrap_or(false);
+                if has_idle_callback {
+                    window
+                        .request_idle_callback(closure.as_ref().unchecked_ref())
+                        .expect("should register `requestIdleCallback` OK");
+                } else {
+                    crate::request_animation_frame(&closure);
+                }
+                closure.forget();
+            }
+            Scheduling::Custom(schedule) => schedule(Box::new(execute)),
+        }
+    }
+}
+
+fn request_animation_frame<F>(execute: F)
+where
+    F: FnOnce() + 'static,
+{
+    let closure_raf: Closure<dyn FnMut()> = Closure::once(execute);
+    crate::request_animation_frame(&closure_raf);
+    closure_raf.forget();
+}
+
+impl fmt::Debug for Scheduling {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Scheduling::Sync => write!(f, "Sync"),
+            Scheduling::AnimationFrame => write!(f, "AnimationFrame"),
+            Scheduling::IdleCallback => write!(f, "IdleCallback"),
+            Scheduling::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
@@ -0,0 +1,182 @@
+#![deny(warnings)]
+use futures::Future;
+use sauron::{
+    html::{
+        attributes::*,
+        events::*,
+        *,
+    },
+    Cmd,
+    Component,
+    Dispatch,
+    Node,
+    Program,
+    Scheduling,
+};
+use std::{
+    cell::Cell,
+    rc::Rc,
+};
+use wasm_bindgen::{
+    JsCast,
+    JsValue,
+};
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, PartialEq)]
+enum CounterMsg {
+    Increment,
+    Mounted,
+}
+
+struct Counter {
+    count: u32,
+    mounted_in_document: Rc<Cell<bool>>,
+}
+
+impl Component<CounterMsg> for Counter {
+    fn update(&mut self, msg: CounterMsg) -> Cmd<Self, CounterMsg> {
+        match msg {
+            CounterMsg::Increment => self.count += 1,
+            CounterMsg::Mounted => (),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<CounterMsg> {
+        let mounted_in_document = Rc::clone(&self.mounted_in_document);
+        span(
+            vec![
+                class("counter"),
+                onclick(|_| CounterMsg::Increment),
+                on_mount(move |element: web_sys::Element| {
+                    mounted_in_document.set(element.is_connected());
+                    CounterMsg::Mounted
+                }),
+            ],
+            vec![text(self.count)],
+        )
+    }
+}
+
+/// the parent wraps the child component in a section once it is updated,
+/// so the host of the child component is replaced
+struct Parent {
+    id: &'static str,
+    renders: u32,
+    mounted_in_document: Rc<Cell<bool>>,
+}
+
+impl Parent {
+    fn new(id: &'static str) -> Self {
+        Parent {
+            id,
+            renders: 0,
+            mounted_in_document: Rc::new(Cell::new(false)),
+        }
+    }
+}
+
+impl Component<()> for Parent {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.renders += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        let mounted_in_document = Rc::clone(&self.mounted_in_document);
+        let counter = component("counter", move || {
+            Counter {
+                count: 42,
+                mounted_in_document,
+            }
+        });
+        div(
+            vec![id(self.id)],
+            vec![
+                text("parent"),
+                if self.renders == 0 {
+                    counter
+                } else {
+                    section(vec![], vec![counter])
+                },
+            ],
+        )
+    }
+}
+
+/// resolves after this many milliseconds
+fn sleep(millis: i32) -> JsFuture {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                &resolve, millis,
+            )
+            .expect("must set the timeout");
+    });
+    JsFuture::from(promise)
+}
+
+fn counter_of(parent_id: &str) -> web_sys::HtmlElement {
+    sauron::document()
+        .query_selector(&format!("#{} .counter", parent_id))
+        .unwrap()
+        .expect("child component must be mounted")
+        .unchecked_into()
+}
+
+#[wasm_bindgen_test]
+fn child_component_is_mounted_in_parent() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::new_append_to_mount(
+        Parent::new("child-component-parent"),
+        &sauron::body(),
+    );
+
+    let counter = counter_of("child-component-parent");
+    assert_eq!(counter.text_content(), Some("42".to_string()));
+    assert!(
+        program.app.borrow().mounted_in_document.get(),
+        "the child component is mounted once the host is in the document"
+    );
+}
+
+#[wasm_bindgen_test(async)]
+fn child_component_keeps_its_state_and_handles_its_msgs(
+) -> impl Future<Item = (), Error = JsValue> {
+    console_error_panic_hook::set_once();
+
+    let parent_id = "child-component-rerendered";
+    let program = Program::mount_to_body_with_scheduling(
+        Parent::new(parent_id),
+        Scheduling::Sync,
+    );
+
+    // the child program updates on the next animation frame
+    counter_of(parent_id).click();
+    sleep(100)
+        .and_then(move |_| {
+            assert_eq!(
+                counter_of(parent_id).text_content(),
+                Some("43".to_string())
+            );
+            assert_eq!(program.app.borrow().renders, 0, "not the parent msg");
+
+            program.dispatch(());
+            assert_eq!(program.app.borrow().renders, 1);
+            let counter = counter_of(parent_id);
+            assert_eq!(counter.text_content(), Some("43".to_string()));
+
+            counter.click();
+            sleep(100).map(move |_| (program, counter))
+        })
+        .map(move |(program, counter)| {
+            assert_eq!(counter.text_content(), Some("44".to_string()));
+            assert_eq!(program.app.borrow().renders, 1);
+            program.unmount();
+        })
+}
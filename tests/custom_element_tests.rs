@@ -0,0 +1,88 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        *,
+    },
+    Cmd,
+    Component,
+    CustomElement,
+    Node,
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Greeting {
+    name: String,
+}
+
+enum Msg {
+    NameChanged(String),
+}
+
+impl Component<Msg> for Greeting {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::NameChanged(name) => self.name = name,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        span(
+            vec![class("greeting")],
+            vec![text(format!("Hello {}", self.name))],
+        )
+    }
+}
+
+impl CustomElement<Msg> for Greeting {
+    fn observed_attributes() -> Vec<&'static str> {
+        vec!["name"]
+    }
+
+    fn attribute_changed(
+        attr_name: &str,
+        _old_value: Option<String>,
+        new_value: Option<String>,
+    ) -> Option<Msg> {
+        match attr_name {
+            "name" => Some(Msg::NameChanged(new_value.unwrap_or_default())),
+            _ => None,
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn custom_element_is_mounted_in_shadow_root() {
+    console_error_panic_hook::set_once();
+
+    sauron::custom_element::register("sauron-greeting", || {
+        Greeting {
+            name: "world".to_string(),
+        }
+    });
+
+    let element: web_sys::HtmlElement = sauron::document()
+        .create_element("sauron-greeting")
+        .expect("must create the custom element")
+        .unchecked_into();
+    sauron::body()
+        .append_child(&element)
+        .expect("must append the custom element");
+
+    let shadow_root = element.shadow_root().expect("must have a shadow root");
+    let greeting = shadow_root
+        .query_selector(".greeting")
+        .unwrap()
+        .expect("must mount the view in the shadow root");
+    assert_eq!(greeting.text_content(), Some("Hello world".to_string()));
+
+    // the program is torn down when the element is disconnected
+    sauron::body()
+        .remove_child(&element)
+        .expect("must remove the custom element");
+    assert_eq!(shadow_root.child_nodes().length(), 0);
+}
@@ -0,0 +1,169 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        *,
+    },
+    Cmd,
+    Component,
+    Debugger,
+    Dispatch,
+    Node,
+    Program,
+    Scheduling,
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Clone)]
+struct Counter {
+    count: i32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "with-serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+enum Msg {
+    Add(i32),
+}
+
+impl Component<Msg> for Counter {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Add(value) => self.count += value,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        div(vec![id("debugger-counter")], vec![text(self.count)])
+    }
+}
+
+fn shown_count() -> Option<String> {
+    sauron::document()
+        .get_element_by_id("debugger-counter")
+        .expect("must be mounted")
+        .text_content()
+}
+
+#[wasm_bindgen_test]
+fn debugger_steps_through_the_recorded_states() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body_with_scheduling(
+        Counter { count: 0 },
+        Scheduling::Sync,
+    );
+    let debugger = program.enable_debugger();
+    program.dispatch(Msg::Add(1));
+    program.dispatch(Msg::Add(10));
+    assert_eq!(debugger.len(), 2);
+    assert_eq!(shown_count(), Some("11".to_string()));
+
+    debugger.step_back();
+    assert!(debugger.is_paused());
+    assert_eq!(debugger.position(), 1);
+    assert_eq!(shown_count(), Some("1".to_string()));
+
+    // msgs are ignored while a past state is shown
+    program.dispatch(Msg::Add(100));
+    assert_eq!(debugger.len(), 2);
+
+    debugger.resume();
+    assert_eq!(shown_count(), Some("11".to_string()));
+
+    let msgs = debugger.export_msgs();
+    assert_eq!(msgs, vec![Msg::Add(1), Msg::Add(10)]);
+    debugger.import_msgs(vec![Msg::Add(5)]);
+    assert_eq!(shown_count(), Some("5".to_string()));
+    program.unmount();
+}
+
+#[derive(Clone)]
+struct Stepper {
+    count: i32,
+    debugger: Rc<RefCell<Option<Debugger<Stepper, StepperMsg>>>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum StepperMsg {
+    Add(i32),
+    StepBack,
+}
+
+impl Component<StepperMsg> for Stepper {
+    fn update(&mut self, msg: StepperMsg) -> Cmd<Self, StepperMsg> {
+        match msg {
+            StepperMsg::Add(value) => self.count += value,
+            StepperMsg::StepBack => {
+                if let Some(debugger) = self.debugger.borrow().as_ref() {
+                    debugger.step_back();
+                }
+            }
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<StepperMsg> {
+        div(vec![], vec![text(self.count)])
+    }
+}
+
+#[wasm_bindgen_test]
+fn debugger_is_not_switched_while_the_msgs_are_executed() {
+    console_error_panic_hook::set_once();
+
+    let debugger = Rc::new(RefCell::new(None));
+    let program = Program::mount_to_body_with_scheduling(
+        Stepper {
+            count: 0,
+            debugger: Rc::clone(&debugger),
+        },
+        Scheduling::Sync,
+    );
+    *debugger.borrow_mut() = Some(program.enable_debugger());
+    program.dispatch(StepperMsg::Add(1));
+    program.dispatch(StepperMsg::StepBack);
+
+    let debugger = debugger.borrow();
+    let debugger = debugger.as_ref().expect("must be enabled");
+    assert!(!debugger.is_paused());
+    assert_eq!(debugger.len(), 2);
+    assert_eq!(program.app.borrow().count, 1);
+    program.unmount();
+}
+
+#[cfg(feature = "with-serde")]
+#[wasm_bindgen_test]
+fn debugger_exports_and_imports_the_msgs_as_json() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body_with_scheduling(
+        Counter { count: 0 },
+        Scheduling::Sync,
+    );
+    let debugger = program.enable_debugger();
+    program.dispatch(Msg::Add(1));
+    program.dispatch(Msg::Add(10));
+
+    let json = debugger.export_json().expect("must serialize");
+    assert_eq!(json, r#"[{"Add":1},{"Add":10}]"#);
+
+    assert!(debugger.import_json("not json").is_err());
+    assert_eq!(debugger.len(), 2);
+
+    debugger
+        .import_json(r#"[{"Add":2},{"Add":3}]"#)
+        .expect("must deserialize");
+    assert_eq!(debugger.msgs(), vec![Msg::Add(2), Msg::Add(3)]);
+    assert_eq!(shown_count(), Some("5".to_string()));
+    program.unmount();
+}
@@ -13,6 +13,7 @@ use std::{
     rc::Rc,
 };
 
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::*;
 
 use web_sys::InputEvent;
@@ -142,3 +143,212 @@ fn updates_active_closures_on_append() {
 
     assert_eq!(&*text.borrow(), "End Text");
 }
+
+// When the DOM is modified outside of the program, the drifted part is rendered anew
+// before patching, so the patches don't land on the wrong nodes.
+#[wasm_bindgen_test]
+fn heals_the_drifted_dom_before_patching() {
+    console_error_panic_hook::set_once();
+
+    let list = |items: Vec<&str>| -> Node<()> {
+        ul(
+            vec![id("drifted-list")],
+            items
+                .into_iter()
+                .map(|item| li(vec![], vec![text(item)]))
+                .collect(),
+        )
+    };
+
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        list(vec!["a", "b"]),
+        &sauron::body(),
+    );
+
+    let list_element = sauron::document()
+        .get_element_by_id("drifted-list")
+        .expect("must have the list");
+    // a third-party script inserts an item in the list
+    let inserted = sauron::document().create_element("li").unwrap();
+    inserted.set_text_content(Some("inserted"));
+    list_element
+        .insert_before(&inserted, list_element.first_child().as_ref())
+        .unwrap();
+
+    dom_updater.update_dom(&simple_program, list(vec!["a", "c"]));
+
+    let list_element = sauron::document()
+        .get_element_by_id("drifted-list")
+        .expect("must have the list");
+    assert_eq!(list_element.inner_html(), "<li>a</li><li>c</li>");
+}
+
+// The children which are added outside of the view to an element marked with
+// `unmanaged_children` are not taken as a drift of the DOM.
+#[wasm_bindgen_test]
+fn keeps_the_unmanaged_children() {
+    console_error_panic_hook::set_once();
+
+    let list = |items: Vec<&str>| -> Node<()> {
+        ul(
+            vec![id("unmanaged-list"), unmanaged_children()],
+            items
+                .into_iter()
+                .map(|item| li(vec![], vec![text(item)]))
+                .collect(),
+        )
+    };
+
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        list(vec!["a", "b"]),
+        &sauron::body(),
+    );
+
+    let list_element = sauron::document()
+        .get_element_by_id("unmanaged-list")
+        .expect("must have the list");
+    // a third-party widget appends an item to the list
+    let appended = sauron::document().create_element("li").unwrap();
+    appended.set_text_content(Some("appended"));
+    list_element.append_child(&appended).unwrap();
+
+    dom_updater.update_dom(&simple_program, list(vec!["a", "c"]));
+
+    assert_eq!(
+        list_element.inner_html(),
+        "<li>a</li><li>c</li><li>appended</li>"
+    );
+}
+
+// Swapping an event listener for another one in the same update keeps
+// the new listener attached to the element.
+#[wasm_bindgen_test]
+fn swaps_the_event_listeners_in_one_update() {
+    console_error_panic_hook::set_once();
+
+    let events = Rc::new(RefCell::new(vec![]));
+    let events_click = Rc::clone(&events);
+    let events_mouseover = Rc::clone(&events);
+    let events_mount = Rc::clone(&events);
+
+    let elem_id = "swaps-the-event-listeners";
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        button(
+            vec![
+                id(elem_id),
+                on_mount(move |_| events_mount.borrow_mut().push("mount")),
+                onclick(move |_| events_click.borrow_mut().push("click")),
+            ],
+            vec![],
+        ),
+        &sauron::body(),
+    );
+
+    dom_updater.update_dom(
+        &simple_program,
+        button(
+            vec![
+                id(elem_id),
+                on_mount(|_| ()),
+                onmouseover(move |_| {
+                    events_mouseover.borrow_mut().push("mouseover")
+                }),
+            ],
+            vec![],
+        ),
+    );
+
+    let button: web_sys::HtmlElement = sauron::document()
+        .get_element_by_id(elem_id)
+        .expect("must have the button")
+        .unchecked_into();
+    button.click();
+    button
+        .dispatch_event(&web_sys::Event::new("mouseover").unwrap())
+        .unwrap();
+
+    assert_eq!(*events.borrow(), vec!["mount", "mouseover"]);
+    assert_eq!(dom_updater.active_closures.values().flatten().count(), 2);
+}
+
+// The element which no longer has event listeners can still be removed
+// without patch errors.
+#[wasm_bindgen_test]
+fn removes_the_element_after_its_listeners_are_removed() {
+    console_error_panic_hook::set_once();
+
+    let list = |items: Vec<Node<()>>| -> Node<()> {
+        ul(vec![id("removed-listeners-list")], items)
+    };
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        list(vec![
+            li(vec![], vec![text("a")]),
+            li(vec![onclick(|_| ())], vec![text("b")]),
+        ]),
+        &sauron::body(),
+    );
+
+    let removed_listener = list(vec![
+        li(vec![], vec![text("a")]),
+        li(vec![], vec![text("b")]),
+    ]);
+    assert_eq!(
+        dom_updater.try_update_dom(&simple_program, removed_listener),
+        Ok(())
+    );
+    assert_eq!(dom_updater.active_closure_len(), 0);
+
+    let truncated = list(vec![li(vec![], vec![text("a")])]);
+    assert_eq!(
+        dom_updater.try_update_dom(&simple_program, truncated),
+        Ok(())
+    );
+
+    let list_element = sauron::document()
+        .get_element_by_id("removed-listeners-list")
+        .expect("must have the list");
+    assert_eq!(list_element.inner_html(), "<li>a</li>");
+}
+
+// The text nodes and their separators are truncated
+#[wasm_bindgen_test]
+fn truncates_the_text_nodes() {
+    console_error_panic_hook::set_once();
+
+    let paragraph = |texts: Vec<&str>| -> Node<()> {
+        p(
+            vec![id("truncated-texts")],
+            texts.into_iter().map(text).collect(),
+        )
+    };
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        paragraph(vec!["a", "b", "c"]),
+        &sauron::body(),
+    );
+    let paragraph_element = sauron::document()
+        .get_element_by_id("truncated-texts")
+        .expect("must have the paragraph");
+    assert_eq!(paragraph_element.inner_html(), "a<!--ptns-->b<!--ptns-->c");
+
+    assert_eq!(
+        dom_updater.try_update_dom(&simple_program, paragraph(vec!["a", "b"])),
+        Ok(())
+    );
+    assert_eq!(paragraph_element.inner_html(), "a<!--ptns-->b");
+
+    assert_eq!(
+        dom_updater.try_update_dom(&simple_program, paragraph(vec![])),
+        Ok(())
+    );
+    assert_eq!(paragraph_element.inner_html(), "");
+}
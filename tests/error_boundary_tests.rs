@@ -0,0 +1,85 @@
+#![deny(warnings)]
+use sauron::{
+    dom::DomUpdater,
+    html::{
+        attributes::*,
+        *,
+    },
+    test_fixtures::simple_program,
+    Node,
+    ViewError,
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Errors = Rc<RefCell<Vec<ViewError>>>;
+
+fn view(failing: bool, errors: &Errors) -> Node<()> {
+    let errors = Rc::clone(errors);
+    div(
+        vec![id("error-boundary-app")],
+        vec![error_boundary(
+            || {
+                if failing {
+                    Err(ViewError::new("unable to render"))
+                } else {
+                    Ok(p(vec![id("error-boundary-content")], vec![]))
+                }
+            },
+            |error| {
+                p(
+                    vec![id("error-boundary-fallback")],
+                    vec![text(error.to_string())],
+                )
+            },
+            move |error| errors.borrow_mut().push(error),
+        )],
+    )
+}
+
+#[wasm_bindgen_test]
+fn the_fallback_is_rendered_and_the_error_is_reported() {
+    console_error_panic_hook::set_once();
+
+    let errors: Errors = Rc::new(RefCell::new(vec![]));
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        view(false, &errors),
+        &body,
+    );
+    let document = sauron::document();
+    assert!(document
+        .get_element_by_id("error-boundary-content")
+        .is_some());
+    assert!(errors.borrow().is_empty());
+
+    dom_updater.update_dom(&simple_program, view(true, &errors));
+    let fallback = document
+        .get_element_by_id("error-boundary-fallback")
+        .expect("must have the fallback");
+    assert_eq!(
+        fallback.text_content(),
+        Some("unable to render".to_string())
+    );
+    assert!(document
+        .get_element_by_id("error-boundary-content")
+        .is_none());
+    assert_eq!(*errors.borrow(), vec![ViewError::new("unable to render")]);
+
+    // the error is not reported again while the fallback stays mounted
+    dom_updater.update_dom(&simple_program, view(true, &errors));
+    assert_eq!(errors.borrow().len(), 1);
+
+    dom_updater.update_dom(&simple_program, view(false, &errors));
+    assert!(document
+        .get_element_by_id("error-boundary-content")
+        .is_some());
+    dom_updater.unmount();
+}
@@ -3,8 +3,13 @@
 
 extern crate wasm_bindgen_test;
 extern crate web_sys;
+use futures::Future;
 use std::rc::Rc;
 use wasm_bindgen_test::*;
+use wasm_bindgen::{
+    JsCast,
+    JsValue,
+};
 
 use sauron::{
     dom::DomUpdater,
@@ -13,10 +18,24 @@ use sauron::{
         events::*,
         *,
     },
-    test_fixtures::simple_program,
+    test_fixtures::{
+        simple_program,
+        SimpleComponent,
+    },
+    Browser,
+    Cmd,
+    Component,
+    Dispatch,
     Node,
+    NodeRef,
+    Program,
+    Scheduling,
+};
+use std::cell::{
+    Cell,
+    RefCell,
 };
-use std::cell::RefCell;
+use wasm_bindgen_futures::JsFuture;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -285,3 +304,883 @@ fn remove_event_from_replaced_node() {
         "There should only be 0 left after replacing it with a different tag"
     );
 }
+
+#[wasm_bindgen_test]
+fn mount_and_unmount_events() {
+    let mounted = Rc::new(RefCell::new(vec![]));
+    let mounted_clone = Rc::clone(&mounted);
+    let unmounted = Rc::new(RefCell::new(vec![]));
+    let unmounted_clone = Rc::clone(&unmounted);
+
+    let old: Node<()> = div(vec![], vec![]);
+    let new: Node<()> = div(
+        vec![],
+        vec![div(
+            vec![
+                id("widget-container"),
+                on_mount(move |element: web_sys::Element| {
+                    mounted_clone.borrow_mut().push(element.id());
+                }),
+                on_unmount(move |element: web_sys::Element| {
+                    unmounted_clone.borrow_mut().push(element.id());
+                }),
+            ],
+            vec![],
+        )],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &body);
+
+    // the container is appended
+    dom_updater.update_dom(&simple_program, new);
+    assert_eq!(*mounted.borrow(), vec!["widget-container".to_string()]);
+    assert!(unmounted.borrow().is_empty());
+
+    // the container is truncated
+    dom_updater.update_dom(&simple_program, div(vec![], vec![]));
+    assert_eq!(*unmounted.borrow(), vec!["widget-container".to_string()]);
+}
+
+#[wasm_bindgen_test]
+fn mouse_events_of_any_type() {
+    let moves = Rc::new(RefCell::new(vec![]));
+    let moves_clone = Rc::clone(&moves);
+
+    let elem_id = "mouse-move-element";
+    let node: Node<()> = div(
+        vec![
+            id(elem_id),
+            onmousemove(move |event: MouseEvent| {
+                moves_clone.borrow_mut().push(event);
+            }),
+        ],
+        vec![],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+
+    let mut init = web_sys::MouseEventInit::new();
+    init.client_x(10)
+        .client_y(20)
+        .button(2)
+        .buttons(3)
+        .related_target(Some(&body));
+    let mouse_event =
+        web_sys::MouseEvent::new_with_mouse_event_init_dict("mousemove", &init)
+            .unwrap();
+    let element = sauron::document().get_element_by_id(elem_id).unwrap();
+    web_sys::EventTarget::from(element)
+        .dispatch_event(&mouse_event)
+        .unwrap();
+
+    let moves = moves.borrow();
+    assert_eq!(moves.len(), 1);
+    let event = &moves[0];
+    assert_eq!(event.r#type, "mousemove");
+    assert_eq!(event.coordinate.client_x, 10);
+    assert_eq!(event.coordinate.client_y, 20);
+    assert_eq!(event.button, MouseButton::Right);
+    assert!(event.is_pressed(&MouseButton::Left));
+    assert!(event.is_pressed(&MouseButton::Right));
+    assert!(!event.is_pressed(&MouseButton::Middle));
+    assert_eq!(
+        event.related_target,
+        Some(web_sys::EventTarget::from(body.clone()))
+    );
+}
+
+#[wasm_bindgen_test]
+fn pointer_events() {
+    let downs = Rc::new(RefCell::new(vec![]));
+    let downs_clone = Rc::clone(&downs);
+
+    let elem_id = "pointer-down-element";
+    let node: Node<()> = div(
+        vec![
+            id(elem_id),
+            onpointerdown(move |event: PointerEvent| {
+                downs_clone.borrow_mut().push(event);
+            }),
+        ],
+        vec![],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+
+    let mut init = web_sys::PointerEventInit::new();
+    init.pointer_id(7)
+        .pointer_type("pen")
+        .pressure(0.5)
+        .tilt_x(30)
+        .is_primary(true);
+    let pointer_event =
+        web_sys::PointerEvent::new_with_event_init_dict("pointerdown", &init)
+            .unwrap();
+    let element = sauron::document().get_element_by_id(elem_id).unwrap();
+    web_sys::EventTarget::from(element)
+        .dispatch_event(&pointer_event)
+        .unwrap();
+
+    let downs = downs.borrow();
+    assert_eq!(downs.len(), 1);
+    let event = &downs[0];
+    assert_eq!(event.mouse.r#type, "pointerdown");
+    assert_eq!(event.pointer_id, 7);
+    assert_eq!(event.pointer_type, "pen");
+    assert_eq!(event.pressure, 0.5);
+    assert_eq!(event.tilt_x, 30);
+    assert!(event.is_primary);
+}
+
+#[wasm_bindgen_test]
+fn drag_and_drop_events() {
+    let drops = Rc::new(RefCell::new(vec![]));
+    let drops_clone = Rc::clone(&drops);
+
+    let source_id = "drag-source-element";
+    let target_id = "drop-target-element";
+    let node: Node<()> = div(
+        vec![],
+        vec![
+            div(
+                vec![
+                    id(source_id),
+                    draggable(true),
+                    ondragstart_with(
+                        DragData::new("text/plain", "card-1")
+                            .effect_allowed("move"),
+                        |_| (),
+                    ),
+                ],
+                vec![],
+            ),
+            div(
+                vec![
+                    id(target_id),
+                    ondrop(move |event: DragEvent| {
+                        drops_clone.borrow_mut().push(event);
+                    }),
+                ],
+                vec![],
+            ),
+        ],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+
+    let data_transfer = web_sys::DataTransfer::new().unwrap();
+    let mut init = web_sys::DragEventInit::new();
+    init.data_transfer(Some(&data_transfer));
+    let drag_start =
+        web_sys::DragEvent::new_with_event_init_dict("dragstart", &init)
+            .unwrap();
+    let source = sauron::document().get_element_by_id(source_id).unwrap();
+    web_sys::EventTarget::from(source)
+        .dispatch_event(&drag_start)
+        .unwrap();
+    assert_eq!(data_transfer.get_data("text/plain").unwrap(), "card-1");
+    assert_eq!(data_transfer.effect_allowed(), "move");
+
+    let drop =
+        web_sys::DragEvent::new_with_event_init_dict("drop", &init).unwrap();
+    let target = sauron::document().get_element_by_id(target_id).unwrap();
+    web_sys::EventTarget::from(target)
+        .dispatch_event(&drop)
+        .unwrap();
+
+    let drops = drops.borrow();
+    assert_eq!(drops.len(), 1);
+    let event = &drops[0];
+    assert_eq!(event.mouse.r#type, "drop");
+    assert_eq!(event.data_transfer.types, vec!["text/plain".to_string()]);
+    assert_eq!(event.data_transfer.get_data("text/plain"), Some("card-1"));
+    assert!(event.data_transfer.files.is_empty());
+}
+
+#[wasm_bindgen_test]
+fn wheel_and_keyboard_event_detail() {
+    let wheels = Rc::new(RefCell::new(vec![]));
+    let wheels_clone = Rc::clone(&wheels);
+    let keys = Rc::new(RefCell::new(vec![]));
+    let keys_clone = Rc::clone(&keys);
+
+    let elem_id = "wheel-key-element";
+    let node: Node<()> = div(
+        vec![
+            id(elem_id),
+            onwheel(move |event: WheelEvent| {
+                wheels_clone.borrow_mut().push(event);
+            }),
+            onkeydown(move |event: KeyEvent| {
+                keys_clone.borrow_mut().push(event);
+            }),
+        ],
+        vec![],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let element: web_sys::EventTarget =
+        sauron::document().get_element_by_id(elem_id).unwrap().into();
+
+    let mut wheel_init = web_sys::WheelEventInit::new();
+    wheel_init.delta_y(120.0).delta_mode(1);
+    let wheel_event =
+        web_sys::WheelEvent::new_with_event_init_dict("wheel", &wheel_init)
+            .unwrap();
+    element.dispatch_event(&wheel_event).unwrap();
+
+    let mut key_init = web_sys::KeyboardEventInit::new();
+    key_init.key("ArrowUp").code("ArrowUp");
+    let key_event =
+        web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(
+            "keydown", &key_init,
+        )
+        .unwrap();
+    element.dispatch_event(&key_event).unwrap();
+
+    let wheels = wheels.borrow();
+    assert_eq!(wheels.len(), 1);
+    assert_eq!(wheels[0].delta_y, 120.0);
+    assert_eq!(wheels[0].delta_mode, DeltaMode::Line);
+
+    let keys = keys.borrow();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].code, "ArrowUp");
+    assert_eq!(keys[0].named_key(), Key::ArrowUp);
+    assert!(!keys[0].is_composing);
+    assert_eq!(KeyEvent::new("F5".to_string()).named_key(), Key::F(5));
+    assert_eq!(KeyEvent::new("a".to_string()).named_key(), Key::Character('a'));
+}
+
+#[wasm_bindgen_test]
+fn form_control_events() {
+    let checks = Rc::new(RefCell::new(vec![]));
+    let checks_clone = Rc::clone(&checks);
+    let selections = Rc::new(RefCell::new(vec![]));
+    let selections_clone = Rc::clone(&selections);
+    let submits = Rc::new(RefCell::new(vec![]));
+    let submits_clone = Rc::clone(&submits);
+
+    let node: Node<()> = form(
+        vec![
+            id("form-controls"),
+            onsubmit(move |form_values: FormValues| {
+                submits_clone.borrow_mut().push(form_values);
+            }),
+        ],
+        vec![
+            input(
+                vec![
+                    id("form-checkbox"),
+                    r#type("checkbox"),
+                    name("agree"),
+                    value("yes"),
+                    onchange_checked(move |checked| {
+                        checks_clone.borrow_mut().push(checked);
+                    }),
+                ],
+                vec![],
+            ),
+            select(
+                vec![
+                    id("form-select"),
+                    name("colors"),
+                    multiple(true),
+                    onchange_selected(move |selected| {
+                        selections_clone.borrow_mut().push(selected);
+                    }),
+                ],
+                vec![
+                    option(vec![value("red")], vec![text("Red")]),
+                    option(vec![value("green")], vec![text("Green")]),
+                    option(vec![value("blue")], vec![text("Blue")]),
+                ],
+            ),
+        ],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let document = sauron::document();
+
+    let checkbox: web_sys::HtmlInputElement = document
+        .get_element_by_id("form-checkbox")
+        .unwrap()
+        .unchecked_into();
+    checkbox.set_checked(true);
+    checkbox
+        .dispatch_event(&web_sys::Event::new("change").unwrap())
+        .unwrap();
+    assert_eq!(*checks.borrow(), vec![true]);
+
+    let select: web_sys::HtmlSelectElement = document
+        .get_element_by_id("form-select")
+        .unwrap()
+        .unchecked_into();
+    let options = select.options();
+    options
+        .item(0)
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlOptionElement>()
+        .set_selected(true);
+    options
+        .item(2)
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlOptionElement>()
+        .set_selected(true);
+    select
+        .dispatch_event(&web_sys::Event::new("change").unwrap())
+        .unwrap();
+    assert_eq!(
+        *selections.borrow(),
+        vec![vec!["red".to_string(), "blue".to_string()]]
+    );
+
+    let form = document.get_element_by_id("form-controls").unwrap();
+    form.dispatch_event(&web_sys::Event::new("submit").unwrap())
+        .unwrap();
+    let submits = submits.borrow();
+    assert_eq!(submits.len(), 1);
+    assert_eq!(submits[0].get("agree"), Some("yes"));
+    assert_eq!(
+        submits[0].get_all("colors"),
+        &["red".to_string(), "blue".to_string()]
+    );
+}
+
+#[wasm_bindgen_test]
+fn form_control_mappers_on_other_elements() {
+    let values = Rc::new(RefCell::new(vec![]));
+    let checked_clone = Rc::clone(&values);
+    let selected_clone = Rc::clone(&values);
+    let number_clone = Rc::clone(&values);
+
+    let elem_id = "not-a-form-control";
+    let node: Node<()> = div(
+        vec![
+            id(elem_id),
+            onchange_checked(move |checked| {
+                checked_clone.borrow_mut().push(format!("{}", checked));
+            }),
+            onchange_selected(move |selected| {
+                selected_clone.borrow_mut().push(format!("{:?}", selected));
+            }),
+            oninput_number(move |number| {
+                number_clone.borrow_mut().push(format!("{:?}", number));
+            }),
+        ],
+        vec![],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let element = sauron::document().get_element_by_id(elem_id).unwrap();
+    element
+        .dispatch_event(&web_sys::Event::new("change").unwrap())
+        .unwrap();
+    element
+        .dispatch_event(&web_sys::Event::new("input").unwrap())
+        .unwrap();
+
+    assert_eq!(
+        *values.borrow(),
+        vec!["false".to_string(), "[]".to_string(), "None".to_string()]
+    );
+}
+
+#[wasm_bindgen_test]
+fn clipboard_focus_and_composition_events() {
+    let pastes = Rc::new(RefCell::new(vec![]));
+    let pastes_clone = Rc::clone(&pastes);
+    let blurs = Rc::new(RefCell::new(vec![]));
+    let blurs_clone = Rc::clone(&blurs);
+    let compositions = Rc::new(RefCell::new(vec![]));
+    let compositions_clone = Rc::clone(&compositions);
+
+    let node: Node<()> = div(
+        vec![],
+        vec![
+            input(
+                vec![
+                    id("ime-input"),
+                    onpaste(move |event: ClipboardEvent| {
+                        pastes_clone.borrow_mut().push(event);
+                    }),
+                    onblur(move |event: FocusEvent| {
+                        blurs_clone.borrow_mut().push(event);
+                    }),
+                    oncompositionupdate(move |event: CompositionEvent| {
+                        compositions_clone.borrow_mut().push(event);
+                    }),
+                ],
+                vec![],
+            ),
+            input(vec![id("next-input")], vec![]),
+        ],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let document = sauron::document();
+    let ime_input = document.get_element_by_id("ime-input").unwrap();
+    let next_input = document.get_element_by_id("next-input").unwrap();
+
+    let data_transfer = web_sys::DataTransfer::new().unwrap();
+    data_transfer.set_data("text/plain", "pasted text").unwrap();
+    let paste = web_sys::Event::new("paste").unwrap();
+    js_sys::Reflect::set(&paste, &"clipboardData".into(), &data_transfer)
+        .unwrap();
+    ime_input.dispatch_event(&paste).unwrap();
+    assert_eq!(
+        *pastes.borrow(),
+        vec![ClipboardEvent {
+            r#type: "paste".to_string(),
+            data: Some("pasted text".to_string()),
+        }]
+    );
+
+    let blur = web_sys::FocusEvent::new_with_focus_event_init_dict(
+        "blur",
+        web_sys::FocusEventInit::new().related_target(Some(&next_input)),
+    )
+    .unwrap();
+    ime_input.dispatch_event(&blur).unwrap();
+    assert_eq!(
+        blurs.borrow()[0].related_target_id,
+        Some("next-input".to_string())
+    );
+
+    let composition = web_sys::CompositionEvent::new_with_event_init_dict(
+        "compositionupdate",
+        web_sys::CompositionEventInit::new().data("にほん"),
+    )
+    .unwrap();
+    ime_input.dispatch_event(&composition).unwrap();
+    assert_eq!(
+        *compositions.borrow(),
+        vec![CompositionEvent {
+            r#type: "compositionupdate".to_string(),
+            data: "にほん".to_string(),
+        }]
+    );
+}
+
+#[wasm_bindgen_test]
+fn media_animation_and_transition_events() {
+    let media_events = Rc::new(RefCell::new(vec![]));
+    let media_events_clone = Rc::clone(&media_events);
+    let animations = Rc::new(RefCell::new(vec![]));
+    let animations_clone = Rc::clone(&animations);
+    let transitions = Rc::new(RefCell::new(vec![]));
+    let transitions_clone = Rc::clone(&transitions);
+
+    let video_ref = NodeRef::new();
+    let node: Node<()> = div(
+        vec![
+            id("animated"),
+            onanimationend(move |event: AnimationEvent| {
+                animations_clone.borrow_mut().push(event);
+            }),
+            ontransitionend(move |event: TransitionEvent| {
+                transitions_clone.borrow_mut().push(event);
+            }),
+        ],
+        vec![video(
+            vec![
+                id("media-video"),
+                node_ref(&video_ref),
+                ontimeupdate(move |event: MediaEvent| {
+                    media_events_clone.borrow_mut().push(event);
+                }),
+            ],
+            vec![],
+        )],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+    let document = sauron::document();
+
+    let set_volume: Cmd<SimpleComponent, ()> =
+        Browser::set_media_volume(&video_ref, 0.25);
+    set_volume.emit(&simple_program);
+    let video = document.get_element_by_id("media-video").unwrap();
+    video
+        .dispatch_event(&web_sys::Event::new("timeupdate").unwrap())
+        .unwrap();
+    let media_event = media_events.borrow()[0].clone();
+    assert_eq!(media_event.r#type, "timeupdate");
+    assert_eq!(media_event.volume, 0.25);
+    assert!(media_event.paused);
+
+    let animated = document.get_element_by_id("animated").unwrap();
+    let animation_end = web_sys::AnimationEvent::new_with_event_init_dict(
+        "animationend",
+        web_sys::AnimationEventInit::new()
+            .animation_name("fade-in")
+            .elapsed_time(1.5),
+    )
+    .unwrap();
+    animated.dispatch_event(&animation_end).unwrap();
+    assert_eq!(
+        *animations.borrow(),
+        vec![AnimationEvent {
+            r#type: "animationend".to_string(),
+            animation_name: "fade-in".to_string(),
+            elapsed_time: 1.5,
+            pseudo_element: "".to_string(),
+        }]
+    );
+
+    let transition_end = web_sys::TransitionEvent::new_with_event_init_dict(
+        "transitionend",
+        web_sys::TransitionEventInit::new()
+            .property_name("opacity")
+            .elapsed_time(0.5),
+    )
+    .unwrap();
+    animated.dispatch_event(&transition_end).unwrap();
+    assert_eq!(transitions.borrow()[0].property_name, "opacity");
+    assert_eq!(transitions.borrow()[0].elapsed_time, 0.5);
+}
+
+#[wasm_bindgen_test]
+fn custom_events() {
+    let details = Rc::new(RefCell::new(vec![]));
+    let details_clone = Rc::clone(&details);
+
+    let chart_ref = NodeRef::new();
+    let node: Node<()> = div(
+        vec![
+            id("custom-event-listener"),
+            on_custom("chart-select", move |detail: JsValue| {
+                details_clone.borrow_mut().push(detail.as_f64());
+            }),
+        ],
+        vec![div(vec![node_ref(&chart_ref)], vec![])],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+
+    let listener = sauron::document()
+        .get_element_by_id("custom-event-listener")
+        .unwrap();
+    let chart_select = web_sys::CustomEvent::new_with_event_init_dict(
+        "chart-select",
+        web_sys::CustomEventInit::new().detail(&JsValue::from(3)),
+    )
+    .unwrap();
+    listener.dispatch_event(&chart_select).unwrap();
+    assert_eq!(*details.borrow(), vec![Some(3.0)]);
+
+    // the event dispatched from the child element bubbles to the listener
+    let dispatch: Cmd<SimpleComponent, ()> = Browser::dispatch_custom_event(
+        &chart_ref,
+        "chart-select",
+        JsValue::from(7),
+    );
+    dispatch.emit(&simple_program);
+    assert_eq!(*details.borrow(), vec![Some(3.0), Some(7.0)]);
+}
+
+#[cfg(feature = "with-serde")]
+#[derive(Debug, PartialEq, serde_derive::Deserialize)]
+struct Selection {
+    start: u32,
+    end: u32,
+}
+
+#[cfg(feature = "with-serde")]
+#[wasm_bindgen_test]
+fn custom_events_with_serde() {
+    let selections = Rc::new(RefCell::new(vec![]));
+    let selections_clone = Rc::clone(&selections);
+
+    let node: Node<()> = div(
+        vec![
+            id("custom-event-with-serde"),
+            on_custom_with_serde(
+                "editor-select",
+                move |selection: Result<Selection, _>| {
+                    selections_clone.borrow_mut().push(selection.ok());
+                },
+            ),
+        ],
+        vec![],
+    );
+
+    let body = sauron::body();
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, node, &body);
+
+    let listener = sauron::document()
+        .get_element_by_id("custom-event-with-serde")
+        .unwrap();
+    let detail = js_sys::JSON::parse(r#"{"start":2,"end":5}"#).unwrap();
+    let editor_select = web_sys::CustomEvent::new_with_event_init_dict(
+        "editor-select",
+        web_sys::CustomEventInit::new().detail(&detail),
+    )
+    .unwrap();
+    listener.dispatch_event(&editor_select).unwrap();
+
+    // a detail that doesn't match the struct is passed as an error
+    let invalid_select = web_sys::CustomEvent::new_with_event_init_dict(
+        "editor-select",
+        web_sys::CustomEventInit::new().detail(&JsValue::from_str("oops")),
+    )
+    .unwrap();
+    listener.dispatch_event(&invalid_select).unwrap();
+
+    assert_eq!(
+        *selections.borrow(),
+        vec![Some(Selection { start: 2, end: 5 }), None]
+    );
+}
+
+struct Notified {
+    details: Vec<Option<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum NotifiedMsg {
+    Notify(Option<String>),
+}
+
+impl Component<NotifiedMsg> for Notified {
+    fn update(&mut self, msg: NotifiedMsg) -> Cmd<Self, NotifiedMsg> {
+        match msg {
+            NotifiedMsg::Notify(detail) => self.details.push(detail),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<NotifiedMsg> {
+        div(
+            vec![on_custom("app-notify", |detail: JsValue| {
+                NotifiedMsg::Notify(detail.as_string())
+            })],
+            vec![text(self.details.len())],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn custom_events_from_the_root() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body_with_scheduling(
+        Notified { details: vec![] },
+        Scheduling::Sync,
+    );
+
+    let notify: Cmd<Notified, NotifiedMsg> =
+        Browser::dispatch_custom_event_from_root(
+            "app-notify",
+            JsValue::from_str("saved"),
+        );
+    notify.emit(&program);
+    assert_eq!(
+        program.app.borrow().details,
+        vec![Some("saved".to_string())]
+    );
+    program.unmount();
+}
+
+struct Clicks {
+    count: u32,
+    views: Rc<Cell<u32>>,
+}
+
+impl Component<()> for Clicks {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.count += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        self.views.set(self.views.get() + 1);
+        div(
+            vec![id("clicks")],
+            vec![
+                button(
+                    vec![id("click-none"), onclick(|_| None).optional()],
+                    vec![],
+                ),
+                button(
+                    vec![
+                        id("click-twice"),
+                        onclick(|_| vec![(), ()]).multiple(),
+                    ],
+                    vec![],
+                ),
+                text(self.count),
+            ],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn callbacks_can_dispatch_no_msg_or_multiple_msgs() {
+    console_error_panic_hook::set_once();
+
+    let views = Rc::new(Cell::new(0));
+    let program = Program::mount_to_body_with_scheduling(
+        Clicks {
+            count: 0,
+            views: Rc::clone(&views),
+        },
+        Scheduling::Sync,
+    );
+    let document = sauron::document();
+    let click = |element_id: &str| {
+        document
+            .get_element_by_id(element_id)
+            .expect("must have the button")
+            .unchecked_into::<web_sys::HtmlElement>()
+            .click();
+    };
+    let views_after_mount = views.get();
+
+    click("click-none");
+    assert_eq!(views.get(), views_after_mount, "no view for no msg");
+
+    click("click-twice");
+    assert_eq!(views.get(), views_after_mount + 1, "one view for both msgs");
+    let clicks = document.get_element_by_id("clicks").expect("must exist");
+    assert_eq!(clicks.text_content(), Some("2".to_string()));
+
+    program.dispatch_multiple(vec![]);
+    assert_eq!(views.get(), views_after_mount + 1);
+    program.unmount();
+}
+
+struct RateLimited {
+    clicks: u32,
+    inputs: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RateLimitedMsg {
+    Click,
+    Input(String),
+}
+
+impl Component<RateLimitedMsg> for RateLimited {
+    fn update(&mut self, msg: RateLimitedMsg) -> Cmd<Self, RateLimitedMsg> {
+        match msg {
+            RateLimitedMsg::Click => self.clicks += 1,
+            RateLimitedMsg::Input(value) => self.inputs.push(value),
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<RateLimitedMsg> {
+        div(
+            vec![],
+            vec![
+                button(
+                    vec![
+                        id("throttled"),
+                        onclick(|_| RateLimitedMsg::Click).throttle(60_000),
+                    ],
+                    vec![],
+                ),
+                input(
+                    vec![
+                        id("debounced"),
+                        oninput(|input: InputEvent| {
+                            RateLimitedMsg::Input(input.value)
+                        })
+                        .debounce(20),
+                    ],
+                    vec![],
+                ),
+            ],
+        )
+    }
+}
+
+/// resolves after this many milliseconds
+fn sleep(millis: i32) -> JsFuture {
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        sauron::window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                &resolve, millis,
+            )
+            .expect("must set the timeout");
+    });
+    JsFuture::from(promise)
+}
+
+#[wasm_bindgen_test(async)]
+fn throttled_and_debounced_listeners() -> impl Future<Item = (), Error = JsValue>
+{
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body_with_scheduling(
+        RateLimited {
+            clicks: 0,
+            inputs: vec![],
+        },
+        Scheduling::Sync,
+    );
+    let document = sauron::document();
+
+    let throttled: web_sys::HtmlElement = document
+        .get_element_by_id("throttled")
+        .expect("must have the button")
+        .unchecked_into();
+    throttled.click();
+    throttled.click();
+    throttled.click();
+    assert_eq!(program.app.borrow().clicks, 1, "only the first click");
+
+    let debounced: web_sys::HtmlInputElement = document
+        .get_element_by_id("debounced")
+        .expect("must have the input")
+        .unchecked_into();
+    for value in &["a", "ab", "abc"] {
+        debounced.set_value(value);
+        debounced
+            .dispatch_event(&web_sys::Event::new("input").unwrap())
+            .unwrap();
+    }
+    assert!(program.app.borrow().inputs.is_empty(), "still pending");
+
+    sleep(100).map(move |_| {
+        assert_eq!(
+            program.app.borrow().inputs,
+            vec!["abc".to_string()],
+            "only the last input"
+        );
+        program.unmount();
+    })
+}
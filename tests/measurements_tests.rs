@@ -0,0 +1,122 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        events::*,
+        *,
+    },
+    Cmd,
+    Component,
+    Dispatch,
+    Measurements,
+    Node,
+    Program,
+    Scheduling,
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Toggle {
+    on: bool,
+}
+
+impl Component<()> for Toggle {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.on = !self.on;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        if self.on {
+            div(
+                vec![],
+                vec![button(vec![onclick(|_| ())], vec![text("on")])],
+            )
+        } else {
+            div(vec![], vec![text("off")])
+        }
+    }
+}
+
+#[wasm_bindgen_test]
+fn measurements_are_delivered_to_the_sink() {
+    console_error_panic_hook::set_once();
+
+    let records: Rc<RefCell<Vec<Measurements>>> = Rc::new(RefCell::new(vec![]));
+    let records_clone = Rc::clone(&records);
+    let program = Program::mount_to_body_with_scheduling(
+        Toggle { on: false },
+        Scheduling::Sync,
+    );
+    program.set_measurements_sink(move |measurements| {
+        records_clone.borrow_mut().push(measurements.clone())
+    });
+
+    program.dispatch(());
+    program.dispatch(());
+
+    let records = records.borrow();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].msg_count, 1);
+    assert_eq!(records[0].patch_count, 1);
+    assert_eq!(records[0].node_count, 3);
+    assert_eq!(records[0].closures_created, 1);
+    assert_eq!(records[1].closures_dropped, 1);
+    program.unmount();
+}
+
+struct Drifting {
+    count: u32,
+}
+
+impl Component<()> for Drifting {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.count += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        ul(
+            vec![id("measured-drift")],
+            vec![
+                li(vec![onclick(|_| ())], vec![text(self.count)]),
+                li(vec![onclick(|_| ())], vec![text("b")]),
+            ],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn closures_of_the_drifted_nodes_are_measured() {
+    console_error_panic_hook::set_once();
+
+    let records: Rc<RefCell<Vec<Measurements>>> = Rc::new(RefCell::new(vec![]));
+    let records_clone = Rc::clone(&records);
+    let program = Program::mount_to_body_with_scheduling(
+        Drifting { count: 0 },
+        Scheduling::Sync,
+    );
+    program.set_measurements_sink(move |measurements| {
+        records_clone.borrow_mut().push(measurements.clone())
+    });
+
+    // a third-party script appends an item to the list,
+    // the list is rendered anew with new closures before it is patched
+    let list = sauron::document()
+        .get_element_by_id("measured-drift")
+        .expect("must have the list");
+    list.append_child(&sauron::document().create_element("li").unwrap())
+        .unwrap();
+
+    program.dispatch(());
+
+    let records = records.borrow();
+    assert_eq!(records[0].closures_created, 2);
+    assert_eq!(records[0].closures_dropped, 2);
+    program.unmount();
+}
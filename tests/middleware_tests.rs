@@ -0,0 +1,80 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        *,
+    },
+    Cmd,
+    Component,
+    Dispatch,
+    Middleware,
+    Node,
+    Program,
+    Scheduling,
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Counter {
+    count: i32,
+}
+
+impl Component<i32> for Counter {
+    fn update(&mut self, value: i32) -> Cmd<Self, i32> {
+        self.count += value;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<i32> {
+        div(vec![id("middleware-counter")], vec![text(self.count)])
+    }
+}
+
+/// drops the negative values and logs the counts that are rendered
+struct OnlyPositive {
+    rendered: Rc<RefCell<Vec<i32>>>,
+}
+
+impl Middleware<Counter, i32> for OnlyPositive {
+    fn before_update(&self, _app: &Counter, value: i32) -> Option<i32> {
+        if value < 0 {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    fn before_patch(&self, app: &Counter, _view: &Node<i32>) {
+        self.rendered.borrow_mut().push(app.count);
+    }
+}
+
+#[wasm_bindgen_test]
+fn middleware_can_drop_msgs() {
+    console_error_panic_hook::set_once();
+
+    let rendered = Rc::new(RefCell::new(vec![]));
+    let program = Program::mount_to_body_with_scheduling(
+        Counter { count: 0 },
+        Scheduling::Sync,
+    );
+    program.add_middleware(OnlyPositive {
+        rendered: Rc::clone(&rendered),
+    });
+    program.dispatch(5);
+    program.dispatch(-3);
+    program.dispatch(2);
+
+    // nothing is rendered for the dropped msg
+    assert_eq!(*rendered.borrow(), vec![5, 7]);
+    let counter = sauron::document()
+        .get_element_by_id("middleware-counter")
+        .expect("must be mounted");
+    assert_eq!(counter.text_content(), Some("7".to_string()));
+    program.unmount();
+}
@@ -0,0 +1,97 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        *,
+    },
+    test_fixtures::simple_program,
+    *,
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn node_ref_is_bound_and_unbound() {
+    console_error_panic_hook::set_once();
+
+    let canvas_ref = NodeRef::new();
+    let old: Node<()> = div(
+        vec![],
+        vec![canvas(
+            vec![id("node-ref-canvas"), node_ref(&canvas_ref)],
+            vec![],
+        )],
+    );
+
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &sauron::body());
+
+    let element = canvas_ref.get().expect("must be bound");
+    assert_eq!(element.id(), "node-ref-canvas");
+    assert!(canvas_ref.cast::<web_sys::HtmlElement>().is_some());
+
+    // the canvas is removed, the node_ref should be unbound
+    dom_updater.update_dom(&simple_program, div(vec![], vec![]));
+    assert_eq!(canvas_ref.get(), None);
+}
+
+#[wasm_bindgen_test]
+fn node_ref_listener_is_notified() {
+    console_error_panic_hook::set_once();
+
+    let events = Rc::new(RefCell::new(vec![]));
+    let events_clone = Rc::clone(&events);
+    let input_ref = NodeRef::with_listener(move |element| {
+        events_clone
+            .borrow_mut()
+            .push(element.map(|element| element.tag_name()));
+    });
+
+    let old: Node<()> =
+        div(vec![], vec![input(vec![node_ref(&input_ref)], vec![])]);
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &sauron::body());
+
+    // the input is replaced by a textarea which uses the same node_ref
+    dom_updater.update_dom(
+        &simple_program,
+        div(vec![], vec![textarea(vec![node_ref(&input_ref)], vec![])]),
+    );
+
+    assert_eq!(
+        *events.borrow(),
+        vec![
+            Some("INPUT".to_string()),
+            None,
+            Some("TEXTAREA".to_string())
+        ]
+    );
+}
+
+#[wasm_bindgen_test]
+fn node_ref_listener_sees_a_mounted_element() {
+    console_error_panic_hook::set_once();
+
+    let connected = Rc::new(RefCell::new(vec![]));
+    let connected_clone = Rc::clone(&connected);
+    let div_ref = NodeRef::with_listener(move |element| {
+        if let Some(element) = element {
+            connected_clone.borrow_mut().push(element.is_connected());
+        }
+    });
+
+    let view: Node<()> =
+        div(vec![], vec![div(vec![node_ref(&div_ref)], vec![])]);
+    let simple_program = simple_program();
+    let _dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, view, &sauron::body());
+
+    assert_eq!(*connected.borrow(), vec![true]);
+}
@@ -0,0 +1,133 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        events::*,
+        *,
+    },
+    Cmd,
+    Component,
+    Dispatch,
+    Node,
+    PatchError,
+    Program,
+    Scheduling,
+};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct App {
+    count: u32,
+}
+
+impl Component<()> for App {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.count += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        let mut attributes = vec![id("patch-error-button")];
+        // the listener is removed and added back on every other update
+        if self.count % 2 == 0 {
+            attributes.push(onclick(|_| ()));
+        }
+        div(
+            vec![id("patch-error-app")],
+            vec![button(
+                attributes,
+                vec![text(format!("item {}", self.count))],
+            )],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn the_dom_is_rendered_anew_when_the_patching_failed() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body_with_scheduling(
+        App { count: 0 },
+        Scheduling::Sync,
+    );
+    let errors: Rc<RefCell<Vec<PatchError>>> = Rc::new(RefCell::new(vec![]));
+    let errors_clone = Rc::clone(&errors);
+    program.set_error_handler(move |error| {
+        errors_clone.borrow_mut().push(error.clone())
+    });
+
+    // the DOM is modified outside of the program,
+    // the closures of the button can no longer be found
+    let button = sauron::document()
+        .get_element_by_id("patch-error-button")
+        .expect("must have the button");
+    button
+        .set_attribute("data-sauron-vdom-id", "modified")
+        .expect("must set the attribute");
+
+    program.dispatch(());
+
+    assert_eq!(
+        *errors.borrow(),
+        vec![PatchError::InvalidVdomId("modified".to_string())]
+    );
+    let button = sauron::document()
+        .get_element_by_id("patch-error-button")
+        .expect("must have the button");
+    assert_eq!(button.inner_html(), "item 1");
+
+    // the recovered DOM is patched as usual
+    program.dispatch(());
+
+    assert_eq!(errors.borrow().len(), 1);
+    assert_eq!(button.inner_html(), "item 2");
+    program.unmount();
+}
+
+struct Shrinking {
+    items: u32,
+}
+
+impl Component<()> for Shrinking {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.items -= 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        ul(
+            vec![id("shrinking-list")],
+            (0..self.items)
+                .map(|item| li(vec![], vec![text(item)]))
+                .collect(),
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn children_removed_outside_of_the_program_are_not_truncated_again() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body_with_scheduling(
+        Shrinking { items: 3 },
+        Scheduling::Sync,
+    );
+    // the DOM is modified outside of the program
+    let list = sauron::document()
+        .get_element_by_id("shrinking-list")
+        .expect("must have the list");
+    list.set_inner_html("");
+
+    program.dispatch(());
+
+    let list = sauron::document()
+        .get_element_by_id("shrinking-list")
+        .expect("must have the list");
+    assert_eq!(list.inner_html(), "<li>0</li><li>1</li>");
+    program.unmount();
+}
@@ -0,0 +1,178 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        events::*,
+        *,
+    },
+    test_fixtures::simple_program,
+    *,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn portal_content_is_mounted_in_target() {
+    console_error_panic_hook::set_once();
+
+    let target = sauron::document().create_element("div").unwrap();
+    target.set_id("portal-target");
+    sauron::body().append_child(&target).unwrap();
+
+    let old: Node<()> = div(
+        vec![id("portal-owner")],
+        vec![portal(
+            "#portal-target",
+            div(vec![class("modal")], vec![text("old")]),
+        )],
+    );
+    let simple_program = simple_program();
+    let mut dom_updater =
+        DomUpdater::new_append_to_mount(&simple_program, old, &sauron::body());
+
+    assert_eq!(target.inner_html(), r#"<div class="modal">old</div>"#);
+    let owner = sauron::document()
+        .get_element_by_id("portal-owner")
+        .unwrap();
+    assert_eq!(owner.query_selector(".modal").unwrap(), None);
+
+    let new: Node<()> = div(
+        vec![id("portal-owner")],
+        vec![portal(
+            "#portal-target",
+            div(vec![class("modal")], vec![text("new")]),
+        )],
+    );
+    dom_updater.update_dom(&simple_program, new);
+    assert_eq!(target.inner_html(), r#"<div class="modal">new</div>"#);
+
+    // the portal content is removed when the portal is no longer in the view
+    dom_updater
+        .update_dom(&simple_program, div(vec![id("portal-owner")], vec![]));
+    assert_eq!(target.inner_html(), "");
+}
+
+#[wasm_bindgen_test]
+fn portal_without_a_target_renders_nothing() {
+    console_error_panic_hook::set_once();
+
+    let view = |content: &str| -> Node<()> {
+        div(
+            vec![id("portal-without-target")],
+            vec![portal(
+                "#missing-portal-target",
+                div(vec![class("missing-modal")], vec![text(content)]),
+            )],
+        )
+    };
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        view("old"),
+        &sauron::body(),
+    );
+    assert_eq!(
+        sauron::document().query_selector(".missing-modal").unwrap(),
+        None
+    );
+
+    // the portal is mounted once the target is found
+    let target = sauron::document().create_element("div").unwrap();
+    target.set_id("missing-portal-target");
+    sauron::body().append_child(&target).unwrap();
+    dom_updater.update_dom(&simple_program, view("new"));
+    assert_eq!(
+        target.inner_html(),
+        r#"<div class="missing-modal">new</div>"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn patch_errors_in_portals_are_returned() {
+    console_error_panic_hook::set_once();
+
+    let target = sauron::document().create_element("div").unwrap();
+    target.set_id("portal-error-target");
+    sauron::body().append_child(&target).unwrap();
+
+    let view = |clickable: bool| -> Node<()> {
+        let mut attributes = vec![id("portal-error-button")];
+        if clickable {
+            attributes.push(onclick(|_| ()));
+        }
+        div(
+            vec![],
+            vec![portal("#portal-error-target", button(attributes, vec![]))],
+        )
+    };
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        view(true),
+        &sauron::body(),
+    );
+
+    // the DOM is modified outside of the program,
+    // the closures of the button can no longer be found
+    sauron::document()
+        .get_element_by_id("portal-error-button")
+        .expect("must have the button")
+        .set_attribute("data-sauron-vdom-id", "modified")
+        .expect("must set the attribute");
+
+    assert_eq!(
+        dom_updater.try_update_dom(&simple_program, view(false)),
+        Err(PatchError::InvalidVdomId("modified".to_string()))
+    );
+    // the portal content is rendered anew
+    assert_eq!(
+        target.inner_html(),
+        r#"<button id="portal-error-button"></button>"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn portal_content_in_a_target_of_the_same_view_is_kept() {
+    console_error_panic_hook::set_once();
+
+    let view = |count: u32| -> Node<()> {
+        div(
+            vec![],
+            vec![
+                div(vec![id("in-view-portal-target")], vec![text(count)]),
+                portal(
+                    "#in-view-portal-target",
+                    span(vec![class("portal-content")], vec![text(count)]),
+                ),
+            ],
+        )
+    };
+    let simple_program = simple_program();
+    let mut dom_updater = DomUpdater::new_append_to_mount(
+        &simple_program,
+        view(0),
+        &sauron::body(),
+    );
+    // the target is not in the document yet when the view is first mounted,
+    // the portal is mounted in the next update
+    dom_updater.update_dom(&simple_program, view(1));
+    let target = sauron::document()
+        .get_element_by_id("in-view-portal-target")
+        .expect("must have the target");
+    assert_eq!(
+        target.inner_html(),
+        r#"1<span class="portal-content">1</span>"#
+    );
+
+    // the portal content is not taken as a drift of the target
+    assert_eq!(dom_updater.try_update_dom(&simple_program, view(2)), Ok(()));
+    assert_eq!(
+        target.inner_html(),
+        r#"2<span class="portal-content">2</span>"#
+    );
+    let target_after = sauron::document()
+        .get_element_by_id("in-view-portal-target")
+        .expect("must have the target");
+    assert_eq!(target_after, target, "the target is not rendered anew");
+}
@@ -0,0 +1,144 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        *,
+    },
+    Cmd,
+    Component,
+    Dispatch,
+    Node,
+    Program,
+    Scheduling,
+};
+use std::{
+    cell::{
+        Cell,
+        RefCell,
+    },
+    rc::Rc,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct Counter {
+    count: u32,
+}
+
+impl Component<()> for Counter {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        self.count += 1;
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(vec![id("sync-counter")], vec![text(self.count)])
+    }
+}
+
+#[wasm_bindgen_test]
+fn sync_scheduling_renders_right_away() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body_with_scheduling(
+        Counter { count: 0 },
+        Scheduling::Sync,
+    );
+    program.dispatch(());
+    program.dispatch(());
+
+    let counter = sauron::document()
+        .get_element_by_id("sync-counter")
+        .expect("must be mounted");
+    assert_eq!(counter.text_content(), Some("2".to_string()));
+    program.unmount();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum BatchMsg {
+    Add(u32),
+    Added(u32),
+}
+
+struct Batched {
+    total: u32,
+    views: Rc<Cell<u32>>,
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl Component<BatchMsg> for Batched {
+    fn update(&mut self, msg: BatchMsg) -> Cmd<Self, BatchMsg> {
+        match msg {
+            BatchMsg::Add(value) => {
+                self.total += value;
+                self.log.borrow_mut().push(format!("update {}", value));
+                let log = Rc::clone(&self.log);
+                Cmd::new(move |program| {
+                    log.borrow_mut().push(format!("cmd {}", value));
+                    program.dispatch(BatchMsg::Added(value));
+                })
+            }
+            BatchMsg::Added(value) => {
+                self.log.borrow_mut().push(format!("added {}", value));
+                Cmd::none()
+            }
+        }
+    }
+
+    fn view(&self) -> Node<BatchMsg> {
+        self.views.set(self.views.get() + 1);
+        div(vec![id("batched-total")], vec![text(self.total)])
+    }
+}
+
+#[wasm_bindgen_test]
+fn msgs_dispatched_before_the_execution_are_rendered_once() {
+    console_error_panic_hook::set_once();
+
+    let executions: Rc<RefCell<Vec<Box<dyn FnOnce()>>>> =
+        Rc::new(RefCell::new(vec![]));
+    let executions_clone = Rc::clone(&executions);
+    let views = Rc::new(Cell::new(0));
+    let log = Rc::new(RefCell::new(vec![]));
+
+    let program = Program::mount_to_body_with_scheduling(
+        Batched {
+            total: 0,
+            views: Rc::clone(&views),
+            log: Rc::clone(&log),
+        },
+        Scheduling::custom(move |execute| {
+            executions_clone.borrow_mut().push(execute)
+        }),
+    );
+    assert_eq!(views.get(), 1);
+
+    program.dispatch(BatchMsg::Add(1));
+    program.dispatch(BatchMsg::Add(2));
+    program.dispatch(BatchMsg::Add(3));
+    // the execution is scheduled only once for all the msgs
+    assert_eq!(executions.borrow().len(), 1);
+    assert_eq!(program.app.borrow().total, 0);
+
+    let execute = executions.borrow_mut().pop().expect("must be scheduled");
+    execute();
+
+    assert_eq!(program.app.borrow().total, 6);
+    assert_eq!(views.get(), 2, "rendered once for all the msgs");
+    // the cmds are emitted in order and the msgs they dispatched
+    // are executed before the view is rendered
+    assert_eq!(
+        *log.borrow(),
+        vec![
+            "update 1", "cmd 1", "update 2", "cmd 2", "update 3", "cmd 3",
+            "added 1", "added 2", "added 3",
+        ]
+    );
+    assert!(executions.borrow().is_empty(), "nothing else is scheduled");
+    let total = sauron::document()
+        .get_element_by_id("batched-total")
+        .expect("must be mounted");
+    assert_eq!(total.text_content(), Some("6".to_string()));
+    program.unmount();
+}
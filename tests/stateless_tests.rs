@@ -0,0 +1,122 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        events::*,
+        *,
+    },
+    Cmd,
+    Component,
+    Dispatch,
+    Node,
+    Program,
+    Scheduling,
+    StatelessComponent,
+};
+use std::cell::Cell;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+thread_local! {
+    static ROW_VIEW_COUNT: Cell<u32> = Cell::new(0);
+}
+
+fn row_view_count() -> u32 {
+    ROW_VIEW_COUNT.with(|count| count.get())
+}
+
+#[derive(PartialEq)]
+struct Row {
+    index: usize,
+    value: u32,
+}
+
+impl StatelessComponent<Msg> for Row {
+    fn view(&self) -> Node<Msg> {
+        ROW_VIEW_COUNT.with(|count| count.set(count.get() + 1));
+        let index = self.index;
+        li(
+            vec![
+                class("stateless-row"),
+                onclick(move |_| Msg::Increment(index)),
+            ],
+            vec![text(self.value)],
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Msg {
+    Increment(usize),
+}
+
+struct Table {
+    rows: Vec<u32>,
+}
+
+impl Component<Msg> for Table {
+    fn update(&mut self, msg: Msg) -> Cmd<Self, Msg> {
+        match msg {
+            Msg::Increment(index) => self.rows[index] += 1,
+        }
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<Msg> {
+        ul(
+            vec![id("stateless-table")],
+            self.rows
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    stateless(Row {
+                        index,
+                        value: *value,
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn stateless_component_is_rendered_only_when_its_props_changed() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body_with_scheduling(
+        Table {
+            rows: vec![1, 2, 3],
+        },
+        Scheduling::Sync,
+    );
+    assert_eq!(row_view_count(), 3);
+
+    program.dispatch(Msg::Increment(1));
+    // only the row with the changed props is rendered again
+    assert_eq!(row_view_count(), 4);
+
+    let rows = sauron::document()
+        .query_selector_all("#stateless-table .stateless-row")
+        .expect("must query the rows");
+    let values: Vec<Option<String>> = (0..rows.length())
+        .map(|i| rows.get(i).and_then(|row| row.text_content()))
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            Some("1".to_string()),
+            Some("3".to_string()),
+            Some("3".to_string())
+        ]
+    );
+
+    // the events of the stateless component are handled by the parent
+    let last_row: web_sys::HtmlElement =
+        rows.get(2).expect("must have a third row").unchecked_into();
+    last_row.click();
+    assert_eq!(row_view_count(), 5);
+    assert_eq!(last_row.text_content(), Some("4".to_string()));
+    program.unmount();
+}
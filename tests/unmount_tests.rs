@@ -0,0 +1,96 @@
+#![deny(warnings)]
+use sauron::{
+    html::{
+        attributes::*,
+        events::*,
+        *,
+    },
+    Browser,
+    Cmd,
+    Component,
+    Node,
+    Program,
+};
+use std::{
+    cell::Cell,
+    rc::Rc,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+struct App;
+
+impl Component<()> for App {
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(
+            vec![id("unmount-app")],
+            vec![button(vec![onclick(|_| ())], vec![text("click")])],
+        )
+    }
+}
+
+#[wasm_bindgen_test]
+fn unmount_removes_the_view_and_closures() {
+    console_error_panic_hook::set_once();
+
+    let program = Program::mount_to_body(App);
+    assert!(sauron::document()
+        .get_element_by_id("unmount-app")
+        .is_some());
+    assert_eq!(program.dom_updater.borrow().active_closure_len(), 1);
+
+    program.unmount();
+
+    assert!(!program.is_mounted());
+    assert!(sauron::document()
+        .get_element_by_id("unmount-app")
+        .is_none());
+    assert_eq!(program.dom_updater.borrow().active_closure_len(), 0);
+}
+
+/// counts the resize events which are received by the listener
+struct Resizes {
+    count: Rc<Cell<u32>>,
+}
+
+impl Component<()> for Resizes {
+    fn init(&self) -> Cmd<Self, ()> {
+        let count = Rc::clone(&self.count);
+        Browser::onresize(move |_width, _height| count.set(count.get() + 1))
+    }
+
+    fn update(&mut self, _msg: ()) -> Cmd<Self, ()> {
+        Cmd::none()
+    }
+
+    fn view(&self) -> Node<()> {
+        div(vec![], vec![])
+    }
+}
+
+#[wasm_bindgen_test]
+fn unmount_removes_the_resize_listener() {
+    console_error_panic_hook::set_once();
+
+    let count = Rc::new(Cell::new(0));
+    let program = Program::mount_to_body(Resizes {
+        count: Rc::clone(&count),
+    });
+    let resize = || {
+        let event = web_sys::Event::new("resize").expect("must create event");
+        sauron::window()
+            .dispatch_event(&event)
+            .expect("must dispatch event");
+    };
+    resize();
+    assert_eq!(count.get(), 1);
+
+    program.unmount();
+    resize();
+    assert_eq!(count.get(), 1);
+}